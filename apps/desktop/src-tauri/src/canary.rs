@@ -0,0 +1,139 @@
+//! Deep health: a periodic canary request that proves the engine actually
+//! serves, catching an engine whose `/health` is green while its model is
+//! broken.
+//!
+//! The canary only runs while `/health` passes, so "degraded" always means
+//! "up but failing real requests", never just "down". Transitions are
+//! announced as `engine://degraded`.
+
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::config::DeepHealthCheck;
+use crate::sync::LockOrRecover;
+use crate::EngineConfigState;
+
+const DEGRADED_EVENT: &str = "engine://degraded";
+/// How often the thread wakes to see whether a canary is due.
+const TICK_SECS: u64 = 5;
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CanaryResult {
+    pub ok: bool,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+    /// RFC 3339, UTC.
+    pub checked_at: String,
+}
+
+/// Outcome of the latest canary; `None` when deep health is off, `/health`
+/// is failing, or no canary has run yet.
+pub struct DeepHealthState(pub Mutex<Option<CanaryResult>>);
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct DegradedEvent {
+    degraded: bool,
+    error: Option<String>,
+}
+
+impl DeepHealthState {
+    pub fn degraded(&self) -> bool {
+        self.0
+            .lock_or_recover()
+            .as_ref()
+            .is_some_and(|result| !result.ok)
+    }
+}
+
+/// Send the canary and check the response is a 2xx JSON body with a value
+/// at `expect_path`.
+async fn send_canary(
+    client: &reqwest::Client,
+    addr: SocketAddr,
+    check: &DeepHealthCheck,
+) -> Result<(), String> {
+    let response = crate::http::sample_request_builder(client, addr, &check.request)?
+        .send()
+        .await
+        .map_err(|e| format!("Canary request failed: {}", e))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("Canary got HTTP {}", status));
+    }
+    let json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Canary response is not JSON: {}", e))?;
+    match check.expect_path.as_deref() {
+        Some(expr) if !crate::health::has_value(&json, expr) => {
+            Err(format!("Canary response has no value at {}", expr))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn store(app: &AppHandle, result: Option<CanaryResult>) {
+    let state = app.state::<DeepHealthState>();
+    let was_degraded = state.degraded();
+    let degraded = result.as_ref().is_some_and(|result| !result.ok);
+    let error = result.as_ref().and_then(|result| result.error.clone());
+    *state.0.lock_or_recover() = result;
+    if degraded != was_degraded {
+        if degraded {
+            eprintln!(
+                "[SOLAT] WARNING: Engine degraded: /health passes but {}",
+                error.as_deref().unwrap_or("the canary failed")
+            );
+        } else {
+            println!("[SOLAT] Engine no longer degraded");
+        }
+        let event = DegradedEvent { degraded, error };
+        if let Err(e) = app.emit(DEGRADED_EVENT, &event) {
+            eprintln!("[SOLAT] WARNING: Failed to emit degraded: {}", e);
+        }
+    }
+}
+
+pub fn spawn_deep_health(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut last_run: Option<Instant> = None;
+        loop {
+            std::thread::sleep(Duration::from_secs(TICK_SECS));
+
+            let config = app.state::<EngineConfigState>().current();
+            let Some(check) = config.deep_health.clone() else {
+                store(&app, None);
+                continue;
+            };
+            if last_run.is_some_and(|at| at.elapsed() < Duration::from_secs(check.interval_secs)) {
+                continue;
+            }
+            last_run = Some(Instant::now());
+
+            if !crate::probe_health(&app, &config).0 {
+                store(&app, None);
+                continue;
+            }
+            let addr = match crate::engine_addr(&app, &config) {
+                Ok(addr) => addr,
+                Err(_) => continue,
+            };
+            let client = app.state::<crate::http::HttpClient>().0.clone();
+            let start = Instant::now();
+            let outcome =
+                tauri::async_runtime::block_on(async { send_canary(&client, addr, &check).await });
+            store(
+                &app,
+                Some(CanaryResult {
+                    ok: outcome.is_ok(),
+                    latency_ms: crate::telemetry::millis(start.elapsed()),
+                    error: outcome.err(),
+                    checked_at: chrono::Utc::now().to_rfc3339(),
+                }),
+            );
+        }
+    });
+}
@@ -0,0 +1,131 @@
+//! Checking that the engine serves the routes this app version calls.
+//!
+//! The engine's FastAPI schema (`/openapi.json`) is the source of truth.
+//! An engine that hides its schema can instead list what it serves under
+//! `capabilities` in `/version`, as `"METHOD /path"` (or a bare `"/path"`
+//! for any method).
+
+use std::net::SocketAddr;
+
+use crate::http::engine_url;
+
+/// Routes the frontend calls directly; keep in sync with the hooks.
+const REQUIRED_ROUTES: [(&str, &str); 9] = [
+    ("GET", "/health"),
+    ("GET", "/config"),
+    ("GET", "/execution/status"),
+    ("POST", "/execution/connect"),
+    ("POST", "/execution/disconnect"),
+    ("POST", "/execution/arm"),
+    ("POST", "/execution/disarm"),
+    ("POST", "/execution/kill-switch/activate"),
+    ("POST", "/execution/kill-switch/reset"),
+];
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Route {
+    pub method: String,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CompatReport {
+    /// `openapi` or `version`: where the engine's routes came from.
+    pub source: String,
+    pub engine_version: Option<String>,
+    /// Required routes the engine doesn't serve; empty means compatible.
+    pub missing: Vec<Route>,
+    pub compatible: bool,
+}
+
+async fn fetch_json(
+    client: &reqwest::Client,
+    addr: SocketAddr,
+    path: &str,
+) -> Result<Option<serde_json::Value>, String> {
+    let response = client
+        .get(engine_url(addr, path))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach engine: {}", e))?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        return Err(format!("{} failed with HTTP {}", path, response.status()));
+    }
+    response
+        .json()
+        .await
+        .map(Some)
+        .map_err(|e| format!("{} is not valid JSON: {}", path, e))
+}
+
+fn served_by_openapi(schema: &serde_json::Value, method: &str, path: &str) -> bool {
+    schema
+        .get("paths")
+        .and_then(|paths| paths.get(path))
+        .and_then(|item| item.get(method.to_lowercase()))
+        .is_some()
+}
+
+fn served_by_capabilities(capabilities: &[&str], method: &str, path: &str) -> bool {
+    capabilities
+        .iter()
+        .any(|capability| match capability.trim().split_once(' ') {
+            Some((cap_method, cap_path)) => {
+                cap_method.eq_ignore_ascii_case(method) && cap_path.trim() == path
+            }
+            None => capability.trim() == path,
+        })
+}
+
+fn report(
+    source: &str,
+    engine_version: Option<String>,
+    served: impl Fn(&str, &str) -> bool,
+) -> CompatReport {
+    let missing: Vec<Route> = REQUIRED_ROUTES
+        .iter()
+        .filter(|(method, path)| !served(method, path))
+        .map(|(method, path)| Route {
+            method: method.to_string(),
+            path: path.to_string(),
+        })
+        .collect();
+    CompatReport {
+        source: source.to_string(),
+        engine_version,
+        compatible: missing.is_empty(),
+        missing,
+    }
+}
+
+/// Compare the engine's routes against `REQUIRED_ROUTES`.
+pub async fn check(client: &reqwest::Client, addr: SocketAddr) -> Result<CompatReport, String> {
+    if let Some(schema) = fetch_json(client, addr, "/openapi.json").await? {
+        let version = schema
+            .pointer("/info/version")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+        return Ok(report("openapi", version, |method, path| {
+            served_by_openapi(&schema, method, path)
+        }));
+    }
+
+    let version = fetch_json(client, addr, "/version")
+        .await?
+        .ok_or("Engine serves neither /openapi.json nor /version; cannot check compatibility")?;
+    let capabilities: Vec<&str> = version
+        .get("capabilities")
+        .and_then(|v| v.as_array())
+        .map(|items| items.iter().filter_map(|item| item.as_str()).collect())
+        .unwrap_or_default();
+    let engine_version = version
+        .get("version")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    Ok(report("version", engine_version, |method, path| {
+        served_by_capabilities(&capabilities, method, path)
+    }))
+}
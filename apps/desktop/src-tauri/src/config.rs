@@ -0,0 +1,1013 @@
+//! Launcher configuration persisted as JSON in the app config directory.
+//!
+//! Every field has a default so a missing or partial file still yields a
+//! usable config; a malformed file is ignored with a warning rather than
+//! blocking the engine launch. A few env vars are layered over the file
+//! without being saved to it, and individual launches (safe mode,
+//! profiling) can override both.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr};
+use std::path::{Path, PathBuf};
+
+/// Must match `identifier` in tauri.conf.json so the file lands in the same
+/// directory Tauri reports as `app_config_dir()`.
+const APP_IDENTIFIER: &str = "com.solat.trading";
+const CONFIG_FILE_NAME: &str = "engine-config.json";
+/// The engine's well-known port, used unless the config says otherwise.
+pub const DEFAULT_ENGINE_PORT: u16 = 8765;
+/// Smallest nonzero `memory_limit_mb`: uv and Python reserve a few hundred
+/// MiB of address space just to start.
+const MIN_MEMORY_LIMIT_MB: u64 = 512;
+/// Canary requests run inference, so don't let them hammer the engine.
+const MIN_DEEP_HEALTH_INTERVAL_SECS: u64 = 10;
+
+/// uvicorn flags the launcher sets itself; extra args may not override them.
+const MANAGED_UVICORN_FLAGS: [&str; 3] = ["--host", "--port", "--log-level"];
+/// uvicorn flags that are legitimately passed more than once.
+const REPEATABLE_UVICORN_FLAGS: [&str; 4] = [
+    "--reload-dir",
+    "--reload-include",
+    "--reload-exclude",
+    "--header",
+];
+/// What uvicorn's `--log-level` accepts.
+pub const UVICORN_LOG_LEVELS: [&str; 6] =
+    ["critical", "error", "warning", "info", "debug", "trace"];
+/// Opt-in required before `bind_host` may leave loopback.
+const ALLOW_REMOTE_VAR: &str = "SOLAT_ALLOW_REMOTE";
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct EngineConfig {
+    /// Launch the engine with optional subsystems disabled (`SOLAT_SAFE_MODE=1`).
+    pub safe_mode: bool,
+    /// Request used by the benchmark and other perf probes.
+    pub sample_request: SampleRequest,
+    /// Write each day's boot output to `engine-boot.YYYY-MM-DD.log`, with
+    /// `engine-boot.log` aliased to today's file.
+    pub daily_log_rotation: bool,
+    /// Dated logs older than this are pruned at startup (0 keeps them forever).
+    pub log_retention_days: u32,
+    /// Engine data directory override, passed as `SOLAT_DATA_DIR`. Boot logs
+    /// follow it; `None` keeps the default `engine/data`.
+    pub data_dir: Option<PathBuf>,
+    /// Emit opt-in lifecycle telemetry as Tauri events (never sent over the network).
+    pub telemetry_enabled: bool,
+    /// Let the watchdog relaunch an engine that exits on its own.
+    pub auto_restart: bool,
+    /// Appended verbatim after the managed uvicorn args (e.g. `--backlog`,
+    /// `2048`). `--host`, `--port` and `--log-level` are reserved. Replaced
+    /// by `SOLAT_ENGINE_EXTRA_ARGS`, split like a shell would, from which
+    /// reserved flags are dropped rather than rejected.
+    pub extra_uvicorn_args: Vec<String>,
+    /// Scheduling priority for the engine; `low` keeps the desktop responsive
+    /// during heavy jobs.
+    pub process_priority: ProcessPriority,
+    /// Run the engine under `py-spy record`; the profile is written to
+    /// `<data dir>/profiles/` when the engine stops.
+    pub profile: bool,
+    /// Hard cap on the active boot log; past it the log is rotated (or, if
+    /// the engine outpaces rotation, truncated) immediately. 0 disables it.
+    pub max_log_bytes: u64,
+    /// `status` a JSON `/health` body must report to count as healthy (or,
+    /// for a body that isn't JSON, a substring it must contain).
+    pub health_marker: String,
+    /// JSONPath (e.g. `$.checks.db`) of a value in the health body to compare
+    /// against `health_expected` instead of substring-matching the marker.
+    pub health_jsonpath: Option<String>,
+    pub health_expected: Option<String>,
+    /// Prefix for the launch command, e.g. `["ssh", "host", "--"]` or a
+    /// `docker run` with the engine port mapped. The normal command follows
+    /// as arguments with its paths unchanged, so the target needs the engine
+    /// at the same location; env overrides only reach it if the wrapper
+    /// forwards them. Health checks still go to the local engine port.
+    pub launch_wrapper: Vec<String>,
+    /// Host health checks and probes target, e.g. with a wrapper that runs
+    /// the engine elsewhere. `None` means the address in `bind_host`
+    /// (loopback for a wildcard bind); `localhost` means the literal
+    /// 127.0.0.1; a hostname is resolved once and the address cached.
+    pub engine_host: Option<String>,
+    /// Port the engine listens on: a number, or `"auto"` for a free
+    /// ephemeral port picked at each launch (reported via `get_engine_port`
+    /// and the `engine://port` event).
+    pub port: PortSetting,
+    /// Address-space cap for the engine in MiB (0 = none). Linux only:
+    /// past it allocations fail and the engine exits, instead of the machine
+    /// running out of memory.
+    pub memory_limit_mb: u64,
+    /// Where engine output goes: the boot log (`file`), the launcher's own
+    /// terminal (`inherit`, e.g. with a debugger attached), or `both`.
+    pub log_target: LogTarget,
+    /// Command run to completion before each engine spawn, e.g. `["mount",
+    /// "/mnt/models"]`; a non-zero exit aborts the start. Empty = none.
+    pub pre_start_hook: Vec<String>,
+    /// Command run after `stop_engine` has stopped the engine.
+    pub post_stop_hook: Vec<String>,
+    /// Periodic canary request proving the engine actually serves, not just
+    /// that `/health` answers; failures mark the engine degraded. `None`
+    /// disables it.
+    pub deep_health: Option<DeepHealthCheck>,
+    /// Compute device: `cpu`, `cuda`, `cuda:<index>` or `mps`, passed to the
+    /// engine as `SOLAT_DEVICE` (and `CUDA_VISIBLE_DEVICES`). `None` leaves
+    /// the choice to the engine.
+    pub device: Option<String>,
+    /// Periodic restart against slow memory creep, deferred while the
+    /// engine reports in-flight requests. `None` disables it.
+    pub restart_schedule: Option<RestartSchedule>,
+    /// uvicorn `--log-level`, one of `UVICORN_LOG_LEVELS`
+    /// (`SOLAT_ENGINE_LOG_LEVEL`).
+    pub log_level: String,
+    /// Address uvicorn binds (`--host`), an IP literal or `localhost`
+    /// (`SOLAT_ENGINE_HOST`). Anything off loopback exposes the engine to
+    /// the network and is only applied with `SOLAT_ALLOW_REMOTE=1`.
+    pub bind_host: String,
+}
+
+/// Exactly one of the two fields is set.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct RestartSchedule {
+    /// Restart every this many hours.
+    pub every_hours: Option<u64>,
+    /// Restart daily at this local time, `HH:MM`.
+    pub daily_at: Option<String>,
+}
+
+/// `HH:MM` as a time of day.
+pub fn parse_time_of_day(text: &str) -> Result<chrono::NaiveTime, String> {
+    chrono::NaiveTime::parse_from_str(text.trim(), "%H:%M")
+        .map_err(|_| format!("'{}' is not a time of day like 03:30", text))
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct DeepHealthCheck {
+    /// A small, cheap inference request.
+    pub request: SampleRequest,
+    /// JSONPath (e.g. `$.prediction`) that must hold a non-null value in the
+    /// response; without it any 2xx JSON response passes.
+    pub expect_path: Option<String>,
+    pub interval_secs: u64,
+}
+
+impl Default for DeepHealthCheck {
+    fn default() -> Self {
+        Self {
+            request: SampleRequest::default(),
+            expect_path: None,
+            interval_secs: 60,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogTarget {
+    #[default]
+    File,
+    Inherit,
+    Both,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortSetting {
+    Auto,
+    Fixed(u16),
+}
+
+impl serde::Serialize for PortSetting {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            PortSetting::Auto => serializer.serialize_str("auto"),
+            PortSetting::Fixed(port) => serializer.serialize_u16(*port),
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for PortSetting {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match serde_json::Value::deserialize(deserializer)? {
+            serde_json::Value::String(s) if s.eq_ignore_ascii_case("auto") => Ok(PortSetting::Auto),
+            serde_json::Value::Number(n) => n
+                .as_u64()
+                .and_then(|n| u16::try_from(n).ok())
+                .map(PortSetting::Fixed)
+                .ok_or_else(|| serde::de::Error::custom(format!("{} is not a valid port", n))),
+            other => Err(serde::de::Error::custom(format!(
+                "expected a port number or \"auto\", got {}",
+                other
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self {
+            safe_mode: false,
+            sample_request: SampleRequest::default(),
+            daily_log_rotation: false,
+            log_retention_days: 14,
+            data_dir: None,
+            telemetry_enabled: false,
+            auto_restart: true,
+            extra_uvicorn_args: Vec::new(),
+            process_priority: ProcessPriority::Normal,
+            profile: false,
+            max_log_bytes: 100 * 1024 * 1024,
+            health_marker: "healthy".to_string(),
+            health_jsonpath: None,
+            health_expected: None,
+            launch_wrapper: Vec::new(),
+            engine_host: None,
+            port: PortSetting::Fixed(DEFAULT_ENGINE_PORT),
+            memory_limit_mb: 0,
+            log_target: LogTarget::File,
+            pre_start_hook: Vec::new(),
+            post_stop_hook: Vec::new(),
+            deep_health: None,
+            device: None,
+            restart_schedule: None,
+            log_level: "info".to_string(),
+            bind_host: "127.0.0.1".to_string(),
+        }
+    }
+}
+
+/// A representative engine request, kept in config so probes stay engine-agnostic.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct SampleRequest {
+    pub method: String,
+    pub path: String,
+    /// JSON body sent with the request, if any.
+    pub body: Option<serde_json::Value>,
+}
+
+impl Default for SampleRequest {
+    fn default() -> Self {
+        Self {
+            method: "GET".to_string(),
+            path: "/health".to_string(),
+            body: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum ConfigError {
+    /// The path doesn't exist or isn't a directory.
+    DataDirNotFound {
+        path: String,
+    },
+    DataDirNotWritable {
+        path: String,
+        reason: String,
+    },
+    /// A setting has a value the launcher can't use.
+    InvalidField {
+        field: String,
+        message: String,
+    },
+    /// Reading or writing the config file itself failed.
+    Io {
+        message: String,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::DataDirNotFound { path } => {
+                write!(f, "Data directory does not exist: {}", path)
+            }
+            ConfigError::DataDirNotWritable { path, reason } => {
+                write!(f, "Data directory is not writable: {} ({})", path, reason)
+            }
+            ConfigError::InvalidField { field, message } => {
+                write!(f, "Invalid {}: {}", field, message)
+            }
+            ConfigError::Io { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+pub fn config_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(APP_IDENTIFIER)
+        .join(CONFIG_FILE_NAME)
+}
+
+pub fn load_config(path: &Path) -> EngineConfig {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) => return EngineConfig::default(),
+    };
+    match serde_json::from_str(&content) {
+        Ok(config) => {
+            println!("[SOLAT] Loaded config from {}", path.display());
+            config
+        }
+        Err(e) => {
+            eprintln!(
+                "[SOLAT] WARNING: Ignoring malformed config {}: {}",
+                path.display(),
+                e
+            );
+            EngineConfig::default()
+        }
+    }
+}
+
+/// Config fields overridden by env vars, read once at startup. They are
+/// layered over the stored config for each launch but never saved to the
+/// file, so unsetting a var puts the file value back.
+#[derive(Debug, Clone, Default)]
+pub struct EnvOverrides {
+    /// `SOLAT_SAFE_MODE`
+    safe_mode: Option<bool>,
+    /// `SOLAT_DATA_DIR`
+    data_dir: Option<PathBuf>,
+    /// `SOLAT_ENGINE_PORT`
+    port: Option<PortSetting>,
+    /// `SOLAT_ENGINE_LOG_LEVEL`
+    log_level: Option<String>,
+    /// `SOLAT_ENGINE_EXTRA_ARGS`
+    extra_uvicorn_args: Option<Vec<String>>,
+    /// `SOLAT_ENGINE_HOST`
+    bind_host: Option<String>,
+}
+
+impl EnvOverrides {
+    /// Read the override vars that are set, warning about unusable values.
+    /// A rejected value is left out, so the file value stays in effect.
+    pub fn from_env() -> Self {
+        let mut overrides = EnvOverrides::default();
+        if let Ok(value) = std::env::var("SOLAT_SAFE_MODE") {
+            match value.trim().to_lowercase().as_str() {
+                "1" | "true" | "yes" => overrides.safe_mode = Some(true),
+                "0" | "false" | "no" | "" => overrides.safe_mode = Some(false),
+                other => eprintln!(
+                    "[SOLAT] WARNING: Ignoring SOLAT_SAFE_MODE={} (expected 1 or 0)",
+                    other
+                ),
+            }
+        }
+        if let Some(value) = std::env::var_os("SOLAT_DATA_DIR").filter(|v| !v.is_empty()) {
+            overrides.data_dir = Some(PathBuf::from(value));
+        }
+        if let Ok(value) = std::env::var("SOLAT_ENGINE_LOG_LEVEL") {
+            match parse_log_level(&value) {
+                Ok(level) => overrides.log_level = Some(level.to_string()),
+                Err(e) => eprintln!("[SOLAT] WARNING: Ignoring SOLAT_ENGINE_LOG_LEVEL: {}", e),
+            }
+        }
+        if let Ok(value) = std::env::var("SOLAT_ENGINE_HOST") {
+            match parse_bind_host(&value) {
+                Ok(_) => overrides.bind_host = Some(value.trim().to_string()),
+                Err(e) => eprintln!("[SOLAT] WARNING: Ignoring SOLAT_ENGINE_HOST: {}", e),
+            }
+        }
+        if let Ok(value) = std::env::var("SOLAT_ENGINE_EXTRA_ARGS") {
+            match split_shell_words(&value) {
+                Ok(args) => overrides.extra_uvicorn_args = Some(strip_managed_flags(args)),
+                Err(e) => eprintln!("[SOLAT] WARNING: Ignoring SOLAT_ENGINE_EXTRA_ARGS: {}", e),
+            }
+        }
+        if let Ok(value) = std::env::var("SOLAT_ENGINE_PORT") {
+            let value = value.trim();
+            if value.eq_ignore_ascii_case("auto") {
+                overrides.port = Some(PortSetting::Auto);
+            } else {
+                match value.parse::<u16>() {
+                    Ok(port) if port >= 1024 => overrides.port = Some(PortSetting::Fixed(port)),
+                    _ => eprintln!(
+                        "[SOLAT] WARNING: Ignoring SOLAT_ENGINE_PORT={} (expected 1024-65535 or auto)",
+                        value
+                    ),
+                }
+            }
+        }
+        overrides
+    }
+
+    /// Overwrite `config`'s overridden fields with the env values.
+    pub fn apply(&self, config: &mut EngineConfig) {
+        if let Some(safe_mode) = self.safe_mode {
+            config.safe_mode = safe_mode;
+        }
+        if let Some(data_dir) = &self.data_dir {
+            config.data_dir = Some(data_dir.clone());
+        }
+        if let Some(port) = self.port {
+            config.port = port;
+        }
+        if let Some(level) = &self.log_level {
+            config.log_level = level.clone();
+        }
+        if let Some(args) = &self.extra_uvicorn_args {
+            config.extra_uvicorn_args = args.clone();
+        }
+        if let Some(host) = &self.bind_host {
+            config.bind_host = host.clone();
+        }
+    }
+
+    /// Names of the config fields an accepted env value overrides.
+    pub fn fields(&self) -> Vec<&'static str> {
+        [
+            ("safe_mode", self.safe_mode.is_some()),
+            ("data_dir", self.data_dir.is_some()),
+            ("port", self.port.is_some()),
+            ("log_level", self.log_level.is_some()),
+            ("extra_uvicorn_args", self.extra_uvicorn_args.is_some()),
+            ("bind_host", self.bind_host.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(field, set)| set.then_some(field))
+        .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub enum ConfigSource {
+    Default,
+    File,
+    Env,
+    /// A per-launch override, e.g. `start_engine_safe`.
+    Argument,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct EffectiveSetting {
+    pub value: serde_json::Value,
+    pub source: ConfigSource,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct EffectiveConfig {
+    pub config_path: String,
+    /// Keyed by config field name.
+    pub settings: BTreeMap<String, EffectiveSetting>,
+}
+
+/// Each setting in effect and where it came from, highest layer first:
+/// the running engine's launch overrides, then env, then the file at
+/// `path`, then the built-in default. `current` is the stored config with
+/// `env` already applied.
+pub fn effective_config(
+    path: &Path,
+    current: &EngineConfig,
+    env: &EnvOverrides,
+    launched: Option<&EngineConfig>,
+) -> EffectiveConfig {
+    let as_map = |config: &EngineConfig| match serde_json::to_value(config) {
+        Ok(serde_json::Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    };
+    let current_map = as_map(current);
+    let launched_map = launched.map(as_map).unwrap_or_default();
+    let file_map = fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|value| match value {
+            serde_json::Value::Object(map) => Some(map),
+            _ => None,
+        })
+        .unwrap_or_default();
+    let env_fields = env.fields();
+
+    let settings = current_map
+        .into_iter()
+        .map(|(key, value)| {
+            let setting = match launched_map.get(&key) {
+                Some(launch_value) if *launch_value != value => EffectiveSetting {
+                    value: launch_value.clone(),
+                    source: ConfigSource::Argument,
+                },
+                _ => EffectiveSetting {
+                    source: if env_fields.contains(&key.as_str()) {
+                        ConfigSource::Env
+                    } else if file_map.contains_key(&key) {
+                        ConfigSource::File
+                    } else {
+                        ConfigSource::Default
+                    },
+                    value,
+                },
+            };
+            (key, setting)
+        })
+        .collect();
+
+    EffectiveConfig {
+        config_path: path.display().to_string(),
+        settings,
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ConfigDiff {
+    /// Field name; nested settings are dotted, e.g. `sample_request.path`.
+    pub key: String,
+    pub default_value: serde_json::Value,
+    pub current_value: serde_json::Value,
+}
+
+fn collect_diffs(
+    prefix: &str,
+    default: &serde_json::Value,
+    current: &serde_json::Value,
+    diffs: &mut Vec<ConfigDiff>,
+) {
+    if default == current {
+        return;
+    }
+    // Recurse into sections present on both sides, so one changed field of
+    // e.g. `sample_request` is reported (and resettable) on its own
+    if let (serde_json::Value::Object(default_map), serde_json::Value::Object(current_map)) =
+        (default, current)
+    {
+        let mut keys: Vec<&String> = default_map.keys().chain(current_map.keys()).collect();
+        keys.sort();
+        keys.dedup();
+        for key in keys {
+            let null = serde_json::Value::Null;
+            let child = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", prefix, key)
+            };
+            collect_diffs(
+                &child,
+                default_map.get(key).unwrap_or(&null),
+                current_map.get(key).unwrap_or(&null),
+                diffs,
+            );
+        }
+        return;
+    }
+    diffs.push(ConfigDiff {
+        key: prefix.to_string(),
+        default_value: default.clone(),
+        current_value: current.clone(),
+    });
+}
+
+/// Split `text` into words as a POSIX shell would: whitespace separates,
+/// single quotes are literal, double quotes allow `\"` and `\\`, and a
+/// backslash outside quotes escapes the next character.
+pub fn split_shell_words(text: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                words.extend(word.take());
+            }
+            '\'' => {
+                let current = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => return Err("unterminated single quote".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                let current = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => current.push(c),
+                            Some(c) => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                            None => return Err("unterminated double quote".to_string()),
+                        },
+                        Some(c) => current.push(c),
+                        None => return Err("unterminated double quote".to_string()),
+                    }
+                }
+            }
+            '\\' => {
+                let escaped = chars.next().ok_or("trailing backslash")?;
+                word.get_or_insert_with(String::new).push(escaped);
+            }
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+/// `args` without the flags the launcher sets itself (and their values),
+/// logging each one dropped.
+fn strip_managed_flags(args: Vec<String>) -> Vec<String> {
+    let mut kept = Vec::new();
+    let mut args = args.into_iter().peekable();
+    while let Some(arg) = args.next() {
+        let flag = arg.split('=').next().unwrap_or(&arg);
+        if !MANAGED_UVICORN_FLAGS.contains(&flag) {
+            kept.push(arg);
+            continue;
+        }
+        let value = if arg.contains('=') {
+            None
+        } else {
+            args.next_if(|next| !next.starts_with("--"))
+        };
+        eprintln!(
+            "[SOLAT] WARNING: Dropping {}{} from SOLAT_ENGINE_EXTRA_ARGS: the launcher manages it",
+            arg,
+            value.map(|value| format!(" {}", value)).unwrap_or_default()
+        );
+    }
+    kept
+}
+
+/// `level` normalized to one of `UVICORN_LOG_LEVELS`.
+pub fn parse_log_level(level: &str) -> Result<&'static str, String> {
+    let level = level.trim().to_lowercase();
+    UVICORN_LOG_LEVELS
+        .into_iter()
+        .find(|known| *known == level)
+        .ok_or_else(|| {
+            format!(
+                "'{}' is not a log level; use one of {}",
+                level,
+                UVICORN_LOG_LEVELS.join(", ")
+            )
+        })
+}
+
+/// The `--log-level` to launch with; an unusable setting falls back to
+/// `info` rather than failing the spawn.
+pub fn effective_log_level(config: &EngineConfig) -> &'static str {
+    parse_log_level(&config.log_level).unwrap_or_else(|e| {
+        eprintln!("[SOLAT] WARNING: {}; using info", e);
+        "info"
+    })
+}
+
+/// `host` as an address; `localhost` is taken as 127.0.0.1.
+pub fn parse_bind_host(host: &str) -> Result<IpAddr, String> {
+    let host = host.trim();
+    if host.eq_ignore_ascii_case("localhost") {
+        return Ok(IpAddr::V4(Ipv4Addr::LOCALHOST));
+    }
+    host.trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse()
+        .map_err(|_| format!("'{}' is not an IP address (or localhost)", host))
+}
+
+fn remote_allowed() -> bool {
+    std::env::var(ALLOW_REMOTE_VAR)
+        .is_ok_and(|value| matches!(value.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+}
+
+/// The address the engine binds: `bind_host` when it is loopback or remote
+/// access was opted into, else 127.0.0.1.
+pub fn effective_bind_host(config: &EngineConfig) -> IpAddr {
+    match parse_bind_host(&config.bind_host) {
+        Ok(ip) if ip.is_loopback() || remote_allowed() => ip,
+        _ => IpAddr::V4(Ipv4Addr::LOCALHOST),
+    }
+}
+
+/// `effective_bind_host`, logging when the engine ends up network-exposed
+/// or a remote host was clamped back to loopback.
+pub fn announce_bind_host(config: &EngineConfig) -> IpAddr {
+    let ip = effective_bind_host(config);
+    match parse_bind_host(&config.bind_host) {
+        Ok(configured) if configured.is_loopback() => {}
+        Ok(configured) if configured == ip => {
+            eprintln!("[SOLAT] ==============================================================");
+            eprintln!(
+                "[SOLAT] WARNING: The engine is bound to {} and reachable from the network.",
+                ip
+            );
+            eprintln!("[SOLAT] WARNING: Anyone who can reach this machine can drive it.");
+            eprintln!("[SOLAT] ==============================================================");
+        }
+        Ok(configured) => eprintln!(
+            "[SOLAT] WARNING: Not binding the engine to {}: set {}=1 to expose it to the network; using {}",
+            configured, ALLOW_REMOTE_VAR, ip
+        ),
+        Err(e) => eprintln!("[SOLAT] WARNING: bind_host {}; using {}", e, ip),
+    }
+    ip
+}
+
+fn is_env_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// `KEY=VALUE` entries of the env file at `path` (e.g. `engine/.env`), for
+/// the engine's environment. Blank lines, `#` comments and an `export `
+/// prefix are allowed and a value may be quoted; any other line is skipped
+/// with a warning. A missing file is no entries.
+pub fn load_env_file(path: &Path) -> Vec<(String, String)> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let mut entries = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let parsed = line.split_once('=').and_then(|(key, value)| {
+            let key = key.trim();
+            let value = value.trim();
+            let value = ['"', '\'']
+                .iter()
+                .find_map(|quote| value.strip_prefix(*quote)?.strip_suffix(*quote))
+                .unwrap_or(value);
+            is_env_key(key).then(|| (key.to_string(), value.to_string()))
+        });
+        match parsed {
+            Some(entry) => entries.push(entry),
+            None => eprintln!(
+                "[SOLAT] WARNING: Skipping malformed line {} of {} (expected KEY=VALUE)",
+                number + 1,
+                path.display()
+            ),
+        }
+    }
+    entries
+}
+
+/// Env vars selecting `device` for the engine. A CUDA index is applied
+/// through `CUDA_VISIBLE_DEVICES`, under which the chosen GPU is the only one
+/// visible, so `SOLAT_DEVICE` is then plain `cuda`; `cpu` hides every GPU.
+pub fn device_env(device: &str) -> Result<Vec<(&'static str, String)>, String> {
+    let device = device.trim().to_lowercase();
+    let env = match device.as_str() {
+        "cpu" => vec![
+            ("SOLAT_DEVICE", "cpu".to_string()),
+            ("CUDA_VISIBLE_DEVICES", String::new()),
+        ],
+        "cuda" | "mps" => vec![("SOLAT_DEVICE", device.clone())],
+        other => {
+            let index = other.strip_prefix("cuda:").ok_or_else(|| {
+                format!(
+                    "'{}' is not a device; use cpu, cuda, cuda:<index> or mps",
+                    other
+                )
+            })?;
+            let index = index.parse::<u32>().map_err(|_| {
+                format!(
+                    "CUDA device index '{}' must be a non-negative integer",
+                    index
+                )
+            })?;
+            vec![
+                ("SOLAT_DEVICE", "cuda".to_string()),
+                ("CUDA_VISIBLE_DEVICES", index.to_string()),
+            ]
+        }
+    };
+    Ok(env)
+}
+
+/// Settings in `current` that differ from the built-in defaults, sorted by key.
+pub fn diff_from_default(current: &EngineConfig) -> Vec<ConfigDiff> {
+    let mut diffs = Vec::new();
+    if let (Ok(default), Ok(current)) = (
+        serde_json::to_value(EngineConfig::default()),
+        serde_json::to_value(current),
+    ) {
+        collect_diffs("", &default, &current, &mut diffs);
+    }
+    diffs
+}
+
+pub fn save_config(path: &Path, config: &EngineConfig) -> Result<(), ConfigError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| ConfigError::Io {
+            message: format!("Failed to create config dir: {}", e),
+        })?;
+    }
+    let json = serde_json::to_string_pretty(config).map_err(|e| ConfigError::Io {
+        message: format!("Failed to serialize config: {}", e),
+    })?;
+    fs::write(path, json).map_err(|e| ConfigError::Io {
+        message: format!("Failed to write config {}: {}", path.display(), e),
+    })?;
+    println!("[SOLAT] Saved config to {}", path.display());
+    Ok(())
+}
+
+/// Check that `path` is an existing directory we can create files in, and
+/// return its canonical form.
+pub fn validate_data_dir(path: &Path) -> Result<PathBuf, ConfigError> {
+    let resolved = path
+        .canonicalize()
+        .ok()
+        .filter(|p| p.is_dir())
+        .ok_or_else(|| ConfigError::DataDirNotFound {
+            path: path.display().to_string(),
+        })?;
+
+    let probe = resolved.join(".solat-write-test");
+    fs::write(&probe, b"ok").map_err(|e| ConfigError::DataDirNotWritable {
+        path: resolved.display().to_string(),
+        reason: e.to_string(),
+    })?;
+    let _ = fs::remove_file(&probe);
+
+    Ok(resolved)
+}
+
+/// Reject extra uvicorn args that collide with the flags we manage or repeat
+/// a non-repeatable flag, either of which makes uvicorn refuse to start.
+pub fn validate_extra_uvicorn_args(args: &[String]) -> Result<(), ConfigError> {
+    let invalid = |message: String| ConfigError::InvalidField {
+        field: "extra_uvicorn_args".to_string(),
+        message,
+    };
+
+    let mut seen: Vec<&str> = Vec::new();
+    for arg in args.iter().filter(|a| a.starts_with("--")) {
+        let flag = arg.split('=').next().unwrap_or(arg);
+        if MANAGED_UVICORN_FLAGS.contains(&flag) {
+            return Err(invalid(format!(
+                "{} is managed by the launcher and can't be overridden",
+                flag
+            )));
+        }
+        if seen.contains(&flag) && !REPEATABLE_UVICORN_FLAGS.contains(&flag) {
+            return Err(invalid(format!("{} is given more than once", flag)));
+        }
+        seen.push(flag);
+    }
+    Ok(())
+}
+
+/// Every problem with `config`, so a settings UI can show them all at once.
+/// This is the one validation path shared by `validate_engine_config` and
+/// `set_engine_config`.
+pub fn validate_config(config: &EngineConfig) -> Vec<ConfigError> {
+    let mut errors = Vec::new();
+
+    if let Err(e) = validate_extra_uvicorn_args(&config.extra_uvicorn_args) {
+        errors.push(e);
+    }
+    if let Some(data_dir) = &config.data_dir {
+        if let Err(e) = validate_data_dir(data_dir) {
+            errors.push(e);
+        }
+    }
+    if reqwest::Method::from_bytes(config.sample_request.method.to_uppercase().as_bytes()).is_err()
+    {
+        errors.push(ConfigError::InvalidField {
+            field: "sample_request.method".to_string(),
+            message: format!("{} is not an HTTP method", config.sample_request.method),
+        });
+    }
+    if !config.sample_request.path.starts_with('/') {
+        errors.push(ConfigError::InvalidField {
+            field: "sample_request.path".to_string(),
+            message: "must start with /".to_string(),
+        });
+    }
+
+    for (field, argv) in [
+        ("launch_wrapper", &config.launch_wrapper),
+        ("pre_start_hook", &config.pre_start_hook),
+        ("post_stop_hook", &config.post_stop_hook),
+    ] {
+        if argv.first().is_some_and(|program| program.is_empty()) {
+            errors.push(ConfigError::InvalidField {
+                field: field.to_string(),
+                message: "first element must be the program to run".to_string(),
+            });
+        }
+    }
+    if let PortSetting::Fixed(port) = config.port {
+        if port < 1024 {
+            errors.push(ConfigError::InvalidField {
+                field: "port".to_string(),
+                message: format!("{} is a privileged port; use 1024-65535 or \"auto\"", port),
+            });
+        }
+    }
+    if config
+        .engine_host
+        .as_deref()
+        .is_some_and(|host| host.trim().is_empty())
+    {
+        errors.push(ConfigError::InvalidField {
+            field: "engine_host".to_string(),
+            message: "must not be empty (omit it for localhost)".to_string(),
+        });
+    }
+    if let Some(schedule) = &config.restart_schedule {
+        let message = match (schedule.every_hours, schedule.daily_at.as_deref()) {
+            (Some(_), Some(_)) | (None, None) => {
+                Some("set exactly one of every_hours and daily_at".to_string())
+            }
+            (Some(0), None) => Some("every_hours must be at least 1".to_string()),
+            (None, Some(time)) => parse_time_of_day(time).err(),
+            (Some(_), None) => None,
+        };
+        if let Some(message) = message {
+            errors.push(ConfigError::InvalidField {
+                field: "restart_schedule".to_string(),
+                message,
+            });
+        }
+    }
+    if let Err(message) = parse_bind_host(&config.bind_host) {
+        errors.push(ConfigError::InvalidField {
+            field: "bind_host".to_string(),
+            message,
+        });
+    }
+    if let Err(message) = parse_log_level(&config.log_level) {
+        errors.push(ConfigError::InvalidField {
+            field: "log_level".to_string(),
+            message,
+        });
+    }
+    if let Some(Err(message)) = config.device.as_deref().map(device_env) {
+        errors.push(ConfigError::InvalidField {
+            field: "device".to_string(),
+            message,
+        });
+    }
+    if let Some(check) = &config.deep_health {
+        if let Some(Err(message)) = check
+            .expect_path
+            .as_deref()
+            .map(crate::health::parse_json_path)
+        {
+            errors.push(ConfigError::InvalidField {
+                field: "deep_health.expect_path".to_string(),
+                message,
+            });
+        }
+        if check.interval_secs < MIN_DEEP_HEALTH_INTERVAL_SECS {
+            errors.push(ConfigError::InvalidField {
+                field: "deep_health.interval_secs".to_string(),
+                message: format!("must be at least {}", MIN_DEEP_HEALTH_INTERVAL_SECS),
+            });
+        }
+    }
+    if let Some(expr) = &config.health_jsonpath {
+        if let Err(message) = crate::health::parse_json_path(expr) {
+            errors.push(ConfigError::InvalidField {
+                field: "health_jsonpath".to_string(),
+                message,
+            });
+        }
+        if config.health_expected.is_none() {
+            errors.push(ConfigError::InvalidField {
+                field: "health_expected".to_string(),
+                message: "is required when health_jsonpath is set".to_string(),
+            });
+        }
+    } else if config.health_marker.is_empty() {
+        errors.push(ConfigError::InvalidField {
+            field: "health_marker".to_string(),
+            message: "must not be empty".to_string(),
+        });
+    }
+
+    if config.memory_limit_mb != 0 && config.memory_limit_mb < MIN_MEMORY_LIMIT_MB {
+        errors.push(ConfigError::InvalidField {
+            field: "memory_limit_mb".to_string(),
+            message: format!(
+                "must be 0 (no limit) or at least {} MB; less won't start the interpreter",
+                MIN_MEMORY_LIMIT_MB
+            ),
+        });
+    }
+
+    errors
+}
@@ -0,0 +1,254 @@
+//! One-shot startup diagnosis: each stage of a launch checked on its own, so
+//! a user can run it and screenshot where a start breaks.
+//!
+//! Every step is non-destructive: nothing touches the running engine, its
+//! port or its boot log. The optional dry spawn starts a second engine on a
+//! free port with a throwaway log (and without the pre-start hook) and stops
+//! it straight away, so it's skipped while the managed engine is running.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use crate::config::{EngineConfig, PortSetting};
+use crate::memory_log::MemoryLog;
+use crate::venv;
+
+/// Long enough to import the engine with a cold bytecode cache.
+const PROBE_TIMEOUT_SECS: u64 = 60;
+/// How long the dry-spawned engine has to not exit.
+const DRY_SPAWN_SECS: u64 = 3;
+/// The engine's `requires-python`.
+const MIN_PYTHON: (u32, u32) = (3, 11);
+/// `--version` of a binary answers straight away.
+const VERSION_TIMEOUT_SECS: u64 = 10;
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DiagnosticStep {
+    pub name: String,
+    pub ok: bool,
+    pub detail: String,
+    pub duration_ms: u64,
+}
+
+fn step(
+    steps: &mut Vec<DiagnosticStep>,
+    name: &str,
+    check: impl FnOnce() -> Result<String, String>,
+) {
+    let start = Instant::now();
+    let result = check();
+    steps.push(DiagnosticStep {
+        name: name.to_string(),
+        ok: result.is_ok(),
+        detail: result.unwrap_or_else(|e| e),
+        duration_ms: crate::telemetry::millis(start.elapsed()),
+    });
+}
+
+/// `uv run python` in the engine dir when uv is available, else the venv's
+/// interpreter (the same choice `spawn_engine` makes).
+fn python_command(engine_dir: &Path, uv: Option<&Path>) -> Command {
+    match uv {
+        Some(uv) => {
+            let mut command = Command::new(uv);
+            command.args(["run", "python"]).current_dir(engine_dir);
+            command
+        }
+        None => {
+            let venv_python = venv::venv_python(engine_dir);
+            let mut command = Command::new(if venv_python.exists() {
+                venv_python
+            } else {
+                PathBuf::from("python3")
+            });
+            command.current_dir(engine_dir);
+            command
+        }
+    }
+}
+
+fn dry_spawn(config: &EngineConfig) -> Result<String, String> {
+    let port = crate::ephemeral_port()?;
+    let log_path = std::env::temp_dir().join("solat-engine-dry-spawn.log");
+    let mut config = config.clone();
+    config.pre_start_hook.clear();
+    let mut child = crate::spawn_engine(&log_path, &config, port, None, None, &MemoryLog::new())
+        .map_err(|e| e.to_string())?;
+
+    let deadline = Instant::now() + Duration::from_secs(DRY_SPAWN_SECS);
+    let exited = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if Instant::now() >= deadline => break None,
+            Ok(None) => std::thread::sleep(Duration::from_millis(100)),
+            Err(e) => return Err(format!("Failed to check dry-spawned engine: {}", e)),
+        }
+    };
+    // SIGTERM, which uv forwards, so the uvicorn it started goes too; a
+    // SIGKILL would stop only uv and orphan the engine on `port`
+    let _ = crate::process::stop_engine_gracefully(&mut child, crate::STOP_GRACE);
+    match exited {
+        Some(status) => Err(format!(
+            "Engine exited within {}s ({}). Log:\n{}",
+            DRY_SPAWN_SECS,
+            status,
+            crate::logs::read_log_tail(&log_path, 20)
+        )),
+        None => Ok(format!(
+            "Engine stayed up for {}s on port {}, then was stopped",
+            DRY_SPAWN_SECS, port
+        )),
+    }
+}
+
+/// What a first launch needs, as a checklist for the splash screen.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PrereqReport {
+    pub uv_found: bool,
+    pub uv_path: Option<String>,
+    /// `uv --version`, e.g. `uv 0.4.18`.
+    pub uv_version: Option<String>,
+    pub engine_dir_found: bool,
+    pub engine_dir: Option<String>,
+    pub venv_present: bool,
+    /// Interpreter a launch would run: the venv's, else `python3` on PATH.
+    pub python_path: Option<String>,
+    /// `python --version`, e.g. `Python 3.12.4`.
+    pub python_version: Option<String>,
+    /// At least `MIN_PYTHON`; false when no version could be read.
+    pub python_ok: bool,
+    /// Every prerequisite is met.
+    pub ready: bool,
+}
+
+fn version_of(program: &Path) -> Option<String> {
+    match venv::probe(Command::new(program).arg("--version"), VERSION_TIMEOUT_SECS) {
+        Ok((status, out)) if status.success() && !out.is_empty() => Some(out),
+        _ => None,
+    }
+}
+
+/// `(major, minor)` out of `Python 3.12.4`.
+fn parse_python_version(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version.trim().strip_prefix("Python ")?.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+/// Look for everything a launch depends on, without running the engine or
+/// letting uv create anything.
+pub fn prerequisites() -> PrereqReport {
+    let uv = crate::resolve_uv_path();
+    let engine_dir = crate::find_engine_dir();
+    let venv_present = engine_dir
+        .as_ref()
+        .is_some_and(|dir| dir.join(".venv").is_dir());
+    let python = crate::find_python(engine_dir.as_deref(), &mut Vec::new());
+    let python_version = python.as_deref().and_then(version_of);
+    let python_ok = python_version
+        .as_deref()
+        .and_then(parse_python_version)
+        .is_some_and(|version| version >= MIN_PYTHON);
+
+    // uv creates the venv, with a suitable Python, on first run; without uv
+    // a working venv has to exist already
+    let ready = engine_dir.is_some() && (uv.is_some() || (venv_present && python_ok));
+
+    PrereqReport {
+        uv_found: uv.is_some(),
+        uv_version: uv.as_deref().and_then(version_of),
+        uv_path: uv.map(|uv| uv.display().to_string()),
+        engine_dir_found: engine_dir.is_some(),
+        engine_dir: engine_dir.map(|dir| dir.display().to_string()),
+        venv_present,
+        python_path: python.map(|python| python.display().to_string()),
+        python_version,
+        python_ok,
+        ready,
+    }
+}
+
+/// Run every step; later steps that need an earlier result (the engine dir,
+/// uv) report why they were skipped instead of running.
+pub fn run(
+    client: &reqwest::Client,
+    config: &EngineConfig,
+    engine_running: bool,
+    managed_port: u16,
+    dry_spawn_requested: bool,
+) -> Vec<DiagnosticStep> {
+    let mut steps = Vec::new();
+
+    let engine_dir = crate::find_engine_dir();
+    step(&mut steps, "find engine dir", || {
+        engine_dir
+            .as_ref()
+            .map(|dir| dir.display().to_string())
+            .ok_or_else(|| "No engine directory with solat_engine/ found".to_string())
+    });
+
+    let uv = crate::resolve_uv_path();
+    step(&mut steps, "resolve uv", || {
+        uv.as_ref()
+            .map(|uv| uv.display().to_string())
+            .ok_or_else(|| {
+                "uv not found via login shell or fallback locations; launches fall back to python3"
+                    .to_string()
+            })
+    });
+
+    step(&mut steps, "check uv version", || {
+        let uv = uv.as_ref().ok_or("Skipped: uv not found")?;
+        match venv::probe(Command::new(uv).arg("--version"), PROBE_TIMEOUT_SECS) {
+            Ok((status, out)) if status.success() => Ok(out),
+            Ok((status, out)) => Err(format!("{} ({})", out, status)),
+            Err(e) => Err(e),
+        }
+    });
+
+    step(&mut steps, "preflight import", || {
+        let engine_dir = engine_dir.as_ref().ok_or("Skipped: no engine directory")?;
+        let mut command = python_command(engine_dir, uv.as_deref());
+        command.args(["-c", "import solat_engine.main"]);
+        match venv::probe(&mut command, PROBE_TIMEOUT_SECS) {
+            Ok((status, _)) if status.success() => Ok("solat_engine.main imports".to_string()),
+            Ok((status, out)) => Err(format!("Import failed ({}):\n{}", status, out)),
+            Err(e) => Err(e),
+        }
+    });
+
+    step(&mut steps, "check port", || match config.port {
+        PortSetting::Auto => Ok("Port is \"auto\"; a free one is picked per launch".to_string()),
+        PortSetting::Fixed(port) if engine_running && port == managed_port => {
+            Ok(format!("Port {} is held by the managed engine", port))
+        }
+        PortSetting::Fixed(port) if crate::port_is_occupied(port) => {
+            if crate::is_our_engine(client, port) {
+                Err(format!(
+                    "Port {} is held by a stale SOLAT engine, which the next launch replaces",
+                    port
+                ))
+            } else {
+                Err(format!(
+                    "Port {} is in use by another application; the next launch leaves it alone and uses a free port instead",
+                    port
+                ))
+            }
+        }
+        PortSetting::Fixed(port) => Ok(format!("Port {} is free", port)),
+    });
+
+    if dry_spawn_requested {
+        step(&mut steps, "dry spawn", || {
+            if engine_running {
+                return Err("Skipped: the engine is running; stop it first".to_string());
+            }
+            engine_dir.as_ref().ok_or("Skipped: no engine directory")?;
+            dry_spawn(config)
+        });
+    }
+
+    steps
+}
@@ -0,0 +1,156 @@
+//! Errors from starting the engine. They reach the frontend as
+//! `{ code, message, ..details }`, so it can branch on `code` for failures
+//! it has specific recovery advice for and show `message` for the rest.
+
+use std::fmt;
+
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+#[derive(Debug, Clone)]
+pub enum EngineError {
+    /// The engine's port accepts connections but HTTP requests to it never
+    /// get an answer: what a firewall or security tool filtering loopback
+    /// traffic looks like, as opposed to an engine that isn't up yet.
+    FirewallSuspected {
+        port: u16,
+        /// The last transport error seen by the health check.
+        detail: String,
+        guidance: String,
+    },
+    /// No candidate directory holds `solat_engine/`, i.e. a broken install.
+    EngineDirMissing {
+        /// Every directory that was looked in.
+        checked: Vec<String>,
+    },
+    /// uv isn't installed and there's no Python to fall back to either.
+    UvNotFound { message: String },
+    /// The port is held by something that isn't a SOLAT engine, which the
+    /// launcher won't kill.
+    PortOccupiedForeign { port: u16 },
+    /// The launch command itself couldn't be run.
+    SpawnFailed { launcher: String, message: String },
+    /// Another start, stop or restart is still running.
+    Busy { operation: String },
+    /// The engine was started but never reported healthy.
+    HealthTimeout { waited_secs: u64, log_tail: String },
+    /// Any other reason the engine couldn't be started.
+    StartFailed { message: String },
+}
+
+impl EngineError {
+    pub fn firewall_suspected(port: u16, detail: String) -> Self {
+        EngineError::FirewallSuspected {
+            port,
+            detail,
+            guidance: format!(
+                "The engine is listening on 127.0.0.1:{} but does not answer HTTP requests. \
+                 A firewall or security tool is probably blocking local connections: allow \
+                 SOLAT and Python through it (or add an exception for port {}) and start \
+                 the engine again.",
+                port, port
+            ),
+        }
+    }
+}
+
+impl EngineError {
+    /// Stable identifier the frontend matches on.
+    pub fn code(&self) -> &'static str {
+        match self {
+            EngineError::FirewallSuspected { .. } => "firewall_suspected",
+            EngineError::EngineDirMissing { .. } => "engine_dir_missing",
+            EngineError::UvNotFound { .. } => "uv_not_found",
+            EngineError::PortOccupiedForeign { .. } => "port_occupied_foreign",
+            EngineError::SpawnFailed { .. } => "spawn_failed",
+            EngineError::Busy { .. } => "busy",
+            EngineError::HealthTimeout { .. } => "health_timeout",
+            EngineError::StartFailed { .. } => "start_failed",
+        }
+    }
+}
+
+impl fmt::Display for EngineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EngineError::FirewallSuspected {
+                detail, guidance, ..
+            } => write!(f, "{} (last error: {})", guidance, detail),
+            EngineError::EngineDirMissing { checked } => write!(
+                f,
+                "Could not find the engine directory (looked in: {})",
+                checked.join(", ")
+            ),
+            EngineError::UvNotFound { message } => write!(f, "{}", message),
+            EngineError::PortOccupiedForeign { port } => write!(
+                f,
+                "Port {} is occupied by a non-SOLAT process; stop it or choose another port",
+                port
+            ),
+            EngineError::SpawnFailed { launcher, message } => {
+                write!(f, "Failed to spawn engine via {}: {}", launcher, message)
+            }
+            EngineError::Busy { operation } => write!(
+                f,
+                "The engine is busy with another {}; try again when it finishes",
+                operation
+            ),
+            EngineError::HealthTimeout {
+                waited_secs,
+                log_tail,
+            } => write!(
+                f,
+                "Engine not healthy after {}s. Last log lines:\n{}",
+                waited_secs, log_tail
+            ),
+            EngineError::StartFailed { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for EngineError {}
+
+impl Serialize for EngineError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("code", self.code())?;
+        map.serialize_entry("message", &self.to_string())?;
+        match self {
+            EngineError::FirewallSuspected {
+                port,
+                detail,
+                guidance,
+            } => {
+                map.serialize_entry("port", port)?;
+                map.serialize_entry("detail", detail)?;
+                map.serialize_entry("guidance", guidance)?;
+            }
+            EngineError::EngineDirMissing { checked } => {
+                map.serialize_entry("checked", checked)?;
+            }
+            EngineError::PortOccupiedForeign { port } => {
+                map.serialize_entry("port", port)?;
+            }
+            EngineError::SpawnFailed { launcher, .. } => {
+                map.serialize_entry("launcher", launcher)?;
+            }
+            EngineError::Busy { operation } => {
+                map.serialize_entry("operation", operation)?;
+            }
+            EngineError::HealthTimeout {
+                waited_secs,
+                log_tail,
+            } => {
+                map.serialize_entry("waited_secs", waited_secs)?;
+                map.serialize_entry("log_tail", log_tail)?;
+            }
+            EngineError::UvNotFound { .. } | EngineError::StartFailed { .. } => {}
+        }
+        map.end()
+    }
+}
+
+impl From<String> for EngineError {
+    fn from(message: String) -> Self {
+        EngineError::StartFailed { message }
+    }
+}
@@ -0,0 +1,177 @@
+//! Bug-report bundles: the boot logs plus what the launcher resolved about
+//! this machine, zipped into the user's Downloads dir.
+//!
+//! The zip is written by hand (stored, uncompressed) rather than pulling in
+//! an archive crate for one command; logs are text and reports are rare.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, Local, NaiveDateTime, Timelike};
+
+use crate::logs;
+
+#[derive(Debug, serde::Serialize)]
+struct EnvironmentInfo {
+    app_version: &'static str,
+    git_commit: Option<&'static str>,
+    os: &'static str,
+    arch: &'static str,
+    port: u16,
+    engine_dir: Option<String>,
+    uv_path: Option<String>,
+    python_path: Option<String>,
+    exported_at: String,
+}
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                0xEDB8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+static CRC32_TABLE: [u32; 256] = crc32_table();
+
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, byte| {
+        CRC32_TABLE[((crc ^ *byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+/// MS-DOS `(time, date)`, the only timestamp a plain zip entry carries.
+fn dos_time(at: NaiveDateTime) -> (u16, u16) {
+    let time = (at.hour() << 11) | (at.minute() << 5) | (at.second() / 2);
+    let date = ((at.year().clamp(1980, 2107) - 1980) as u32) << 9 | (at.month() << 5) | at.day();
+    (time as u16, date as u16)
+}
+
+fn too_large(name: &str) -> io::Error {
+    io::Error::other(format!("{} is too large for a zip without zip64", name))
+}
+
+/// Write `entries` as an uncompressed zip archive.
+fn write_zip(out: &mut impl Write, entries: &[(String, Vec<u8>)]) -> io::Result<()> {
+    let (time, date) = dos_time(Local::now().naive_local());
+    let mut central = Vec::new();
+    let mut offset: u32 = 0;
+
+    for (name, data) in entries {
+        let size = u32::try_from(data.len()).map_err(|_| too_large(name))?;
+        let name_len = u16::try_from(name.len()).map_err(|_| too_large(name))?;
+        let crc = crc32(data);
+        // version 2.0, UTF-8 names, stored
+        let fields = |header: &mut Vec<u8>| {
+            header.extend_from_slice(&20u16.to_le_bytes());
+            header.extend_from_slice(&0x0800u16.to_le_bytes());
+            header.extend_from_slice(&0u16.to_le_bytes());
+            header.extend_from_slice(&time.to_le_bytes());
+            header.extend_from_slice(&date.to_le_bytes());
+            header.extend_from_slice(&crc.to_le_bytes());
+            header.extend_from_slice(&size.to_le_bytes());
+            header.extend_from_slice(&size.to_le_bytes());
+            header.extend_from_slice(&name_len.to_le_bytes());
+            header.extend_from_slice(&0u16.to_le_bytes());
+        };
+
+        let mut local = 0x0403_4b50u32.to_le_bytes().to_vec();
+        fields(&mut local);
+        local.extend_from_slice(name.as_bytes());
+        out.write_all(&local)?;
+        out.write_all(data)?;
+
+        central.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        central.extend_from_slice(&20u16.to_le_bytes());
+        fields(&mut central);
+        // comment length, disk, internal and external attributes
+        central.extend_from_slice(&[0; 10]);
+        central.extend_from_slice(&offset.to_le_bytes());
+        central.extend_from_slice(name.as_bytes());
+
+        offset = (local.len() as u64 + size as u64)
+            .checked_add(offset as u64)
+            .and_then(|end| u32::try_from(end).ok())
+            .ok_or_else(|| too_large("the archive"))?;
+    }
+
+    let count = u16::try_from(entries.len()).map_err(|_| too_large("the archive"))?;
+    let central_len = u32::try_from(central.len()).map_err(|_| too_large("the archive"))?;
+    out.write_all(&central)?;
+    let mut end = 0x0605_4b50u32.to_le_bytes().to_vec();
+    end.extend_from_slice(&[0; 4]);
+    end.extend_from_slice(&count.to_le_bytes());
+    end.extend_from_slice(&count.to_le_bytes());
+    end.extend_from_slice(&central_len.to_le_bytes());
+    end.extend_from_slice(&offset.to_le_bytes());
+    end.extend_from_slice(&0u16.to_le_bytes());
+    out.write_all(&end)
+}
+
+/// Zip the active and previous boot logs with an `environment.json` of
+/// resolved paths, platform, port and app version into
+/// `solat-diagnostics-<timestamp>.zip` in Downloads, returning its path.
+pub fn export_diagnostics(log_path: &Path, port: u16) -> Result<PathBuf, String> {
+    let downloads = dirs::download_dir()
+        .or_else(dirs::home_dir)
+        .ok_or("Could not find a Downloads directory")?;
+    let now = Local::now();
+
+    let engine_dir = crate::find_engine_dir();
+    let info = EnvironmentInfo {
+        app_version: crate::APP_VERSION,
+        git_commit: crate::GIT_COMMIT,
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        port,
+        uv_path: crate::resolve_uv_path().map(|uv| uv.display().to_string()),
+        python_path: crate::find_python(engine_dir.as_deref(), &mut Vec::new())
+            .map(|python| python.display().to_string()),
+        engine_dir: engine_dir.map(|dir| dir.display().to_string()),
+        exported_at: now.to_rfc3339(),
+    };
+
+    let mut entries = vec![(
+        "environment.json".to_string(),
+        serde_json::to_vec_pretty(&info).map_err(|e| e.to_string())?,
+    )];
+    let logs = [
+        ("engine-boot.log", Some(log_path.to_path_buf())),
+        ("engine-boot.prev.log", logs::previous_log_path(log_path)),
+    ];
+    for (name, path) in logs {
+        let Some(path) = path else {
+            continue;
+        };
+        match fs::read(&path) {
+            Ok(bytes) => entries.push((name.to_string(), bytes)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    let zip_path = downloads.join(format!(
+        "solat-diagnostics-{}.zip",
+        now.format("%Y%m%d-%H%M%S")
+    ));
+    let mut file = fs::File::create(&zip_path)
+        .map_err(|e| format!("Failed to create {}: {}", zip_path.display(), e))?;
+    write_zip(&mut file, &entries)
+        .and_then(|_| file.flush())
+        .map_err(|e| format!("Failed to write {}: {}", zip_path.display(), e))?;
+    println!("[SOLAT] Diagnostics exported to {}", zip_path.display());
+    Ok(zip_path)
+}
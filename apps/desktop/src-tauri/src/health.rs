@@ -0,0 +1,144 @@
+//! Deciding whether a `/health` body means "healthy".
+//!
+//! By default a JSON body's `status` has to equal `health_marker` (a body
+//! that isn't JSON just has to contain it). With `health_jsonpath` set, the
+//! body is parsed as JSON and the value at that path must equal
+//! `health_expected` exactly. Only the subset of JSONPath needed to address
+//! one value is supported: `$`, `.key`, `['key']` and `[index]`, e.g.
+//! `$.checks.db` or `$['checks'][0]`. The same paths check the shape of
+//! deep-health canary responses.
+
+use crate::config::EngineConfig;
+
+/// The engine's `/health` body, `{status, version, time, uptime_seconds}`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct HealthReport {
+    pub status: String,
+    pub version: Option<String>,
+    pub uptime_secs: Option<u64>,
+}
+
+/// `None` unless `json` is an object with a string `status`.
+pub fn parse_report(json: &serde_json::Value) -> Option<HealthReport> {
+    let status = json.get("status")?.as_str()?.to_string();
+    let version = json
+        .get("version")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    let uptime_secs = json.get("uptime_seconds").and_then(|v| {
+        v.as_u64().or_else(|| {
+            v.as_f64()
+                .filter(|secs| *secs >= 0.0)
+                .map(|secs| secs as u64)
+        })
+    });
+    Some(HealthReport {
+        status,
+        version,
+        uptime_secs,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+pub fn parse_json_path(expr: &str) -> Result<Vec<PathSegment>, String> {
+    let rest = expr.trim().strip_prefix('$').ok_or("must start with $")?;
+    let mut segments = Vec::new();
+    let mut chars = rest.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => {
+                let mut key = String::new();
+                while let Some(&next) = chars.peek() {
+                    if next == '.' || next == '[' {
+                        break;
+                    }
+                    key.push(next);
+                    chars.next();
+                }
+                if key.is_empty() {
+                    return Err("empty key after '.'".to_string());
+                }
+                segments.push(PathSegment::Key(key));
+            }
+            '[' => {
+                let mut inner = String::new();
+                loop {
+                    match chars.next() {
+                        Some(']') => break,
+                        Some(next) => inner.push(next),
+                        None => return Err("unclosed '['".to_string()),
+                    }
+                }
+                let inner = inner.trim();
+                let quoted = inner
+                    .strip_prefix('\'')
+                    .and_then(|s| s.strip_suffix('\''))
+                    .or_else(|| inner.strip_prefix('"').and_then(|s| s.strip_suffix('"')));
+                if let Some(key) = quoted {
+                    segments.push(PathSegment::Key(key.to_string()));
+                } else {
+                    let index = inner
+                        .parse::<usize>()
+                        .map_err(|_| format!("'{}' is neither a quoted key nor an index", inner))?;
+                    segments.push(PathSegment::Index(index));
+                }
+            }
+            other => return Err(format!("unexpected '{}'", other)),
+        }
+    }
+    Ok(segments)
+}
+
+fn select<'a>(value: &'a serde_json::Value, path: &[PathSegment]) -> Option<&'a serde_json::Value> {
+    path.iter()
+        .try_fold(value, |current, segment| match segment {
+            PathSegment::Key(key) => current.get(key),
+            PathSegment::Index(index) => current.get(index),
+        })
+}
+
+/// Exact comparison: strings by their content, anything else (`true`,
+/// `200`, `null`) by its JSON text.
+fn value_matches(value: &serde_json::Value, expected: &str) -> bool {
+    match value {
+        serde_json::Value::String(s) => s == expected,
+        other => serde_json::to_string(other).is_ok_and(|text| text == expected),
+    }
+}
+
+/// Whether `expr` resolves to a non-null value in `json`. An invalid path
+/// never does.
+pub fn has_value(json: &serde_json::Value, expr: &str) -> bool {
+    parse_json_path(expr)
+        .ok()
+        .and_then(|path| select(json, &path).map(|value| !value.is_null()))
+        .unwrap_or(false)
+}
+
+pub fn body_is_healthy(config: &EngineConfig, body: &str) -> bool {
+    let Some(expr) = config.health_jsonpath.as_deref() else {
+        // An error body that merely mentions the marker must not pass
+        return match serde_json::from_str::<serde_json::Value>(body) {
+            Ok(json) => {
+                parse_report(&json).is_some_and(|report| report.status == config.health_marker)
+            }
+            Err(_) => body.contains(&config.health_marker),
+        };
+    };
+    // Config validation rejects a bad path or a missing expected value, so
+    // either here (e.g. a hand-edited file) just reads as unhealthy.
+    let (Ok(path), Some(expected)) = (parse_json_path(expr), config.health_expected.as_deref())
+    else {
+        return false;
+    };
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|json| select(&json, &path).map(|value| value_matches(value, expected)))
+        .unwrap_or(false)
+}
@@ -0,0 +1,75 @@
+//! User commands run around the engine's lifetime, for setup the launcher
+//! doesn't know about (mounting a drive, decrypting a model, a sidecar DB).
+//!
+//! `pre_start_hook` runs before every spawn and a failure aborts the start;
+//! `post_stop_hook` runs after `stop_engine`. Each runs to completion in the
+//! engine directory, bounded by `HOOK_TIMEOUT_SECS`, with its output
+//! appended to the boot log (or the launcher's console if there is none).
+
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::process;
+
+const HOOK_TIMEOUT_SECS: u64 = 120;
+
+/// Run the hook `argv` (a no-op when empty), failing if it can't start,
+/// times out or exits non-zero.
+pub fn run(
+    name: &str,
+    argv: &[String],
+    cwd: &Path,
+    mut log: Option<&mut File>,
+) -> Result<(), String> {
+    let Some((program, args)) = argv.split_first() else {
+        return Ok(());
+    };
+    let mut command = Command::new(program);
+    command.args(args).current_dir(cwd);
+
+    let description = process::describe_command(&command);
+    println!("[SOLAT] Running {}: {}", name, description);
+    let (stdout, stderr) = match log.as_deref_mut() {
+        Some(file) => {
+            let _ = writeln!(file, "[SOLAT] Running {}: {}", name, description);
+            let clone = || {
+                file.try_clone()
+                    .map(Stdio::from)
+                    .map_err(|e| format!("Failed to clone log file handle: {}", e))
+            };
+            (clone()?, clone()?)
+        }
+        None => (Stdio::inherit(), Stdio::inherit()),
+    };
+    let result = command
+        .stdin(Stdio::null())
+        .stdout(stdout)
+        .stderr(stderr)
+        .spawn()
+        .map_err(|e| format!("{} failed to start: {}", name, e))
+        .and_then(|mut child| {
+            process::wait_bounded(&mut child, HOOK_TIMEOUT_SECS)
+                .map_err(|e| format!("{} {}", name, e))
+        })
+        .and_then(|status| {
+            if status.success() {
+                Ok(())
+            } else {
+                Err(format!("{} exited with {}", name, status))
+            }
+        });
+
+    let outcome = match &result {
+        Ok(()) => format!("{} finished", name),
+        Err(e) => {
+            eprintln!("[SOLAT] WARNING: {}", e);
+            format!("WARNING: {}", e)
+        }
+    };
+    if let Some(file) = log {
+        let _ = writeln!(file, "[SOLAT] {}", outcome);
+    }
+    result
+}
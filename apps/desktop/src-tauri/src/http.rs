@@ -0,0 +1,489 @@
+//! HTTP access to the running engine: client construction and perf probes.
+
+use std::future::Future;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, ToSocketAddrs};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::config::SampleRequest;
+use crate::sync::LockOrRecover;
+
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+/// Health polls run every half second during boot; a slow answer is as good
+/// as none.
+const HEALTH_TIMEOUT_SECS: u64 = 2;
+/// Engines may redirect their health route (`/health` → `/healthz`); more
+/// hops than this is a loop.
+const MAX_REDIRECTS: usize = 3;
+/// Upper bound on benchmark iterations so a typo can't tie up the engine.
+pub const MAX_BENCH_ITERATIONS: usize = 100;
+
+/// Hostnames resolved so far, so a flaky resolver is consulted once rather
+/// than on every probe.
+static RESOLVED_HOSTS: Mutex<Vec<(String, IpAddr)>> = Mutex::new(Vec::new());
+
+/// Literal address of the engine host. `None` and `localhost` map straight to
+/// 127.0.0.1 (some locked-down systems can't resolve `localhost`, others
+/// lack one of the loopback families); IP literals are used as given.
+pub fn resolve_engine_host(host: Option<&str>) -> Result<IpAddr, String> {
+    let host = match host.map(str::trim) {
+        None => return Ok(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+        Some(host) if host.eq_ignore_ascii_case("localhost") => {
+            return Ok(IpAddr::V4(Ipv4Addr::LOCALHOST))
+        }
+        Some(host) => host,
+    };
+    if let Ok(ip) = host.trim_start_matches('[').trim_end_matches(']').parse() {
+        return Ok(ip);
+    }
+
+    let mut cache = RESOLVED_HOSTS.lock_or_recover();
+    if let Some((_, ip)) = cache.iter().find(|(name, _)| name == host) {
+        return Ok(*ip);
+    }
+    let ip = (host, 0)
+        .to_socket_addrs()
+        .map_err(|e| format!("Could not resolve engine host '{}': {}", host, e))?
+        .map(|addr| addr.ip())
+        .next()
+        .ok_or_else(|| format!("Engine host '{}' resolved to no addresses", host))?;
+    println!("[SOLAT] Resolved engine host {} to {}", host, ip);
+    cache.push((host.to_string(), ip));
+    Ok(ip)
+}
+
+fn is_loopback_url(url: &reqwest::Url) -> bool {
+    match url.host_str() {
+        Some(host) if host.eq_ignore_ascii_case("localhost") => true,
+        Some(host) => host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback()),
+        None => false,
+    }
+}
+
+/// Follow a few redirects, but never off loopback: probes must not be
+/// bounced to another host.
+fn loopback_redirect_policy() -> reqwest::redirect::Policy {
+    reqwest::redirect::Policy::custom(|attempt| {
+        if attempt.previous().len() >= MAX_REDIRECTS {
+            let error = format!("more than {} redirects", MAX_REDIRECTS);
+            attempt.error(error)
+        } else if !is_loopback_url(attempt.url()) {
+            let error = format!("refusing redirect off loopback to {}", attempt.url());
+            attempt.error(error)
+        } else {
+            attempt.follow()
+        }
+    })
+}
+
+/// The one client all engine requests go through, so connections to the
+/// engine are pooled and reused across the UI's frequent polls. Cloning the
+/// inner client is cheap and shares the pool.
+pub struct HttpClient(pub reqwest::Client);
+
+pub fn build_http_client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .redirect(loopback_redirect_policy())
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))
+}
+
+/// Always a literal address (IPv6 bracketed), never a name to resolve.
+pub fn engine_url(addr: SocketAddr, path: &str) -> String {
+    format!("http://{}/{}", addr, path.trim_start_matches('/'))
+}
+
+/// Run `future` to completion from sync code. The sync health helpers are
+/// called from plain threads and from inside async commands alike, and
+/// blocking the async runtime from one of its own workers would panic, so
+/// the future is driven from a scoped thread of its own.
+pub fn block_on<F>(future: F) -> F::Output
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    std::thread::scope(|scope| {
+        match scope
+            .spawn(|| tauri::async_runtime::block_on(future))
+            .join()
+        {
+            Ok(output) => output,
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    })
+}
+
+/// An HTTP answer from `/health`, whatever its status.
+#[derive(Debug, Clone)]
+pub struct HealthResponse {
+    pub status: u16,
+    pub body: String,
+    /// The body parsed as JSON, if it is JSON.
+    pub json: Option<serde_json::Value>,
+}
+
+/// GET `/health`. Errors are transport failures only (refused, timed out,
+/// reset) and say which; a non-2xx answer is still a `HealthResponse`.
+pub async fn check_health(
+    client: &reqwest::Client,
+    addr: SocketAddr,
+) -> Result<HealthResponse, String> {
+    let describe = |e: reqwest::Error| {
+        if e.is_timeout() {
+            format!("timed out after {}s", HEALTH_TIMEOUT_SECS)
+        } else if e.is_connect() {
+            format!("connection refused: {}", e)
+        } else {
+            e.to_string()
+        }
+    };
+    let response = client
+        .get(engine_url(addr, "/health"))
+        .timeout(Duration::from_secs(HEALTH_TIMEOUT_SECS))
+        .send()
+        .await
+        .map_err(describe)?;
+    let status = response.status().as_u16();
+    let body = response.text().await.map_err(describe)?;
+    let json = serde_json::from_str(&body).ok();
+    Ok(HealthResponse { status, body, json })
+}
+
+/// Build (but don't send) the configured sample request.
+pub fn sample_request_builder(
+    client: &reqwest::Client,
+    addr: SocketAddr,
+    sample: &SampleRequest,
+) -> Result<reqwest::RequestBuilder, String> {
+    let method = reqwest::Method::from_bytes(sample.method.to_uppercase().as_bytes())
+        .map_err(|_| format!("Invalid sample request method: {}", sample.method))?;
+    let mut request = client.request(method, engine_url(addr, &sample.path));
+    if let Some(body) = &sample.body {
+        request = request.json(body);
+    }
+    Ok(request)
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct BenchResult {
+    pub iterations: usize,
+    pub min_ms: f64,
+    pub max_ms: f64,
+    pub avg_ms: f64,
+    pub p95_ms: f64,
+}
+
+/// Send the sample request `iterations` times, sequentially so requests don't
+/// skew each other, and aggregate the round-trip timings.
+pub async fn benchmark(
+    client: &reqwest::Client,
+    addr: SocketAddr,
+    sample: &SampleRequest,
+    iterations: usize,
+) -> Result<BenchResult, String> {
+    let iterations = iterations.clamp(1, MAX_BENCH_ITERATIONS);
+    let mut timings_ms = Vec::with_capacity(iterations);
+
+    for i in 0..iterations {
+        let request = sample_request_builder(client, addr, sample)?;
+        let start = Instant::now();
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Benchmark request {} failed: {}", i + 1, e))?;
+        let status = response.status();
+        // Include body transfer in the timing, not just the headers
+        let _ = response.bytes().await;
+        if !status.is_success() {
+            return Err(format!(
+                "Benchmark request {} returned HTTP {}",
+                i + 1,
+                status
+            ));
+        }
+        timings_ms.push(start.elapsed().as_secs_f64() * 1000.0);
+    }
+
+    timings_ms.sort_by(|a, b| a.total_cmp(b));
+    let p95_index = ((timings_ms.len() as f64 * 0.95).ceil() as usize).saturating_sub(1);
+
+    Ok(BenchResult {
+        iterations,
+        min_ms: timings_ms[0],
+        max_ms: timings_ms[timings_ms.len() - 1],
+        avg_ms: timings_ms.iter().sum::<f64>() / timings_ms.len() as f64,
+        p95_ms: timings_ms[p95_index],
+    })
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct FirstResponse {
+    /// Until the first byte of the body (the end of the response if it has
+    /// none). For streaming endpoints this approximates time-to-first-token.
+    pub ttfb_ms: f64,
+    /// Until the status line and headers arrived.
+    pub headers_ms: f64,
+    pub status: u16,
+}
+
+/// Send the sample request once and time the first body chunk, without
+/// waiting for the rest of a streamed response.
+pub async fn measure_first_response(
+    client: &reqwest::Client,
+    addr: SocketAddr,
+    sample: &SampleRequest,
+) -> Result<FirstResponse, String> {
+    let request = sample_request_builder(client, addr, sample)?;
+    let start = Instant::now();
+    let mut response = request
+        .send()
+        .await
+        .map_err(|e| format!("Sample request failed: {}", e))?;
+    let headers_ms = start.elapsed().as_secs_f64() * 1000.0;
+    let status = response.status();
+
+    response
+        .chunk()
+        .await
+        .map_err(|e| format!("Failed to read response body: {}", e))?;
+    let ttfb_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    Ok(FirstResponse {
+        ttfb_ms,
+        headers_ms,
+        status: status.as_u16(),
+    })
+}
+
+/// Proxy-related env vars reqwest reads when building a client.
+const PROXY_ENV_VARS: [&str; 8] = [
+    "HTTP_PROXY",
+    "http_proxy",
+    "HTTPS_PROXY",
+    "https_proxy",
+    "ALL_PROXY",
+    "all_proxy",
+    "NO_PROXY",
+    "no_proxy",
+];
+
+#[derive(Debug, serde::Serialize)]
+pub struct ProxySetting {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct LoopbackReport {
+    pub reachable: bool,
+    pub url: String,
+    pub http_status: Option<u16>,
+    pub error: Option<String>,
+    /// Proxy env vars in effect, credentials redacted.
+    pub proxy_env: Vec<ProxySetting>,
+}
+
+/// Strip `user:pass@` from a proxy URL so it's safe to show and log.
+fn redact_proxy_credentials(value: &str) -> String {
+    match (value.find("://"), value.rfind('@')) {
+        (Some(scheme_end), Some(at)) if at > scheme_end => {
+            format!("{}://***@{}", &value[..scheme_end], &value[at + 1..])
+        }
+        (None, Some(at)) => format!("***@{}", &value[at + 1..]),
+        _ => value.to_string(),
+    }
+}
+
+fn proxy_env() -> Vec<ProxySetting> {
+    PROXY_ENV_VARS
+        .iter()
+        .filter_map(|name| {
+            std::env::var(name).ok().map(|value| ProxySetting {
+                name: name.to_string(),
+                value: redact_proxy_credentials(&value),
+            })
+        })
+        .collect()
+}
+
+/// GET the health URL through the same client the app uses, to diagnose
+/// "engine is running but the app can't reach it" (usually a proxy routing
+/// 127.0.0.1 somewhere it shouldn't).
+pub async fn verify_loopback(client: &reqwest::Client, addr: SocketAddr) -> LoopbackReport {
+    let url = engine_url(addr, "/health");
+    let (reachable, http_status, error) = match client.get(&url).send().await {
+        Ok(response) => {
+            let status = response.status();
+            let error = if status.is_success() {
+                None
+            } else {
+                Some(format!("HTTP {}", status))
+            };
+            (status.is_success(), Some(status.as_u16()), error)
+        }
+        Err(e) => (false, None, Some(e.to_string())),
+    };
+
+    LoopbackReport {
+        reachable,
+        url,
+        http_status,
+        error,
+        proxy_env: proxy_env(),
+    }
+}
+
+/// POST to an engine admin endpoint (e.g. `/admin/pause`). A 404/405 means
+/// this engine build doesn't have the endpoint, reported as "not supported".
+pub async fn post_admin(
+    client: &reqwest::Client,
+    addr: SocketAddr,
+    path: &str,
+) -> Result<(), String> {
+    if post_admin_json(client, addr, path, None).await? {
+        Ok(())
+    } else {
+        Err(format!(
+            "Not supported: this engine has no {} endpoint",
+            path
+        ))
+    }
+}
+
+/// Like `post_admin`, with an optional JSON body. Returns false, rather than
+/// an error, when the engine doesn't have the endpoint (404/405/501).
+pub async fn post_admin_json(
+    client: &reqwest::Client,
+    addr: SocketAddr,
+    path: &str,
+    body: Option<&serde_json::Value>,
+) -> Result<bool, String> {
+    let mut request = client.post(engine_url(addr, path));
+    if let Some(body) = body {
+        request = request.json(body);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach engine: {}", e))?;
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND
+        || status == reqwest::StatusCode::METHOD_NOT_ALLOWED
+        || status == reqwest::StatusCode::NOT_IMPLEMENTED
+    {
+        return Ok(false);
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("{} failed with HTTP {}: {}", path, status, body));
+    }
+    Ok(true)
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct CancelResult {
+    pub accepted: bool,
+    /// HTTP status of the last cancel attempt.
+    pub status: u16,
+    pub message: String,
+}
+
+/// Ask the engine to cancel request `request_id`: `DELETE /requests/{id}`,
+/// then `POST /requests/{id}/cancel` if the engine doesn't take the first.
+/// A well-formed refusal (unknown or already finished request) is reported
+/// as `accepted: false` rather than an error.
+pub async fn cancel_request(
+    client: &reqwest::Client,
+    addr: SocketAddr,
+    request_id: &str,
+) -> Result<CancelResult, String> {
+    let request_id = request_id.trim();
+    // Ids are interpolated into the path, so keep them to URL-safe characters
+    if request_id.is_empty()
+        || !request_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(format!("Invalid request id: '{}'", request_id));
+    }
+    let path = format!("/requests/{}", request_id);
+    let attempts = [
+        (reqwest::Method::DELETE, path.clone()),
+        (reqwest::Method::POST, format!("{}/cancel", path)),
+    ];
+
+    let mut last_status = reqwest::StatusCode::NOT_FOUND;
+    for (method, path) in attempts {
+        let response = client
+            .request(method, engine_url(addr, &path))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach engine: {}", e))?;
+        last_status = response.status();
+        if last_status.is_success() {
+            return Ok(CancelResult {
+                accepted: true,
+                status: last_status.as_u16(),
+                message: format!("Request {} cancelled", request_id),
+            });
+        }
+        // 404 may mean an unknown id rather than a missing route, so it
+        // falls through to the other form as well
+        if !matches!(
+            last_status,
+            reqwest::StatusCode::NOT_FOUND
+                | reqwest::StatusCode::METHOD_NOT_ALLOWED
+                | reqwest::StatusCode::NOT_IMPLEMENTED
+        ) {
+            let body = response.text().await.unwrap_or_default();
+            return Ok(CancelResult {
+                accepted: false,
+                status: last_status.as_u16(),
+                message: format!("Engine refused to cancel {}: {}", request_id, body.trim()),
+            });
+        }
+    }
+    Ok(CancelResult {
+        accepted: false,
+        status: last_status.as_u16(),
+        message: format!(
+            "Engine has no cancel endpoint, or no request {}",
+            request_id
+        ),
+    })
+}
+
+/// Requests the engine is still working on, from `/admin/inflight` (a bare
+/// number, or an object with `inflight`). `None` when the engine has no
+/// such endpoint.
+pub async fn inflight_requests(
+    client: &reqwest::Client,
+    addr: SocketAddr,
+) -> Result<Option<u64>, String> {
+    let response = client
+        .get(engine_url(addr, "/admin/inflight"))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach engine: {}", e))?;
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::METHOD_NOT_ALLOWED
+    {
+        return Ok(None);
+    }
+    if !status.is_success() {
+        return Err(format!("/admin/inflight failed with HTTP {}", status));
+    }
+    let json: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("/admin/inflight is not valid JSON: {}", e))?;
+    json.as_u64()
+        .or_else(|| json.get("inflight").and_then(|v| v.as_u64()))
+        .map(Some)
+        .ok_or_else(|| format!("/admin/inflight has no count: {}", json))
+}
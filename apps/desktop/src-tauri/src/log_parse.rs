@@ -0,0 +1,210 @@
+//! Parsing engine log lines into structured records.
+//!
+//! Recognised formats, tried in order:
+//!
+//! - the engine's JSON logs: `{"timestamp": ..., "level": ..., "message": ...}`
+//! - the engine's text logs: `<iso timestamp> | LEVEL | module | message`
+//! - uvicorn: `LEVEL:     message`
+//! - the launcher's own `[SOLAT] ...` lines
+//!
+//! Anything else is `raw`. Traceback lines never start a record of their
+//! own: they are folded into the record above, or into a record of their
+//! own marked `is_traceback` when a `Traceback (most recent call last)`
+//! header starts one.
+
+const LEVELS: [&str; 5] = ["debug", "info", "warning", "error", "critical"];
+const RAW_LEVEL: &str = "raw";
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LogEntry {
+    /// One of `debug`, `info`, `warning`, `error`, `critical`, or `raw`.
+    pub level: String,
+    pub timestamp: Option<String>,
+    /// The message, with any folded continuation lines joined by `\n`.
+    pub message: String,
+    pub is_traceback: bool,
+}
+
+/// Whether `line` carries on the record above it rather than starting a new
+/// one: indented traceback frames, the traceback header and chaining notes,
+/// and the closing `SomeError: message` line.
+pub fn is_continuation(line: &str) -> bool {
+    if line.starts_with([' ', '\t'])
+        || line.starts_with("Traceback (most recent call last)")
+        || line.starts_with("During handling of the above exception")
+        || line.starts_with("The above exception was the direct cause")
+    {
+        return true;
+    }
+    let name = line.split(':').next().unwrap_or_default();
+    !name.is_empty()
+        && name.len() < line.len()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '.')
+        && ["Error", "Exception", "Warning", "Exit", "Interrupt"]
+            .iter()
+            .any(|suffix| name.ends_with(suffix))
+}
+
+fn normalize_level(level: &str) -> Option<&'static str> {
+    let level = level.trim().to_lowercase();
+    let level = match level.as_str() {
+        "warn" => "warning",
+        "fatal" => "critical",
+        other => other,
+    };
+    LEVELS.iter().find(|known| **known == level).copied()
+}
+
+fn entry(level: &str, timestamp: Option<String>, message: &str) -> LogEntry {
+    LogEntry {
+        level: level.to_string(),
+        timestamp,
+        message: message.to_string(),
+        is_traceback: false,
+    }
+}
+
+fn parse_json(line: &str) -> Option<LogEntry> {
+    if !line.starts_with('{') {
+        return None;
+    }
+    let json: serde_json::Value = serde_json::from_str(line).ok()?;
+    let level = json
+        .get("level")
+        .and_then(|v| v.as_str())
+        .and_then(normalize_level)?;
+    let timestamp = json
+        .get("timestamp")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    let message = json.get("message").and_then(|v| v.as_str()).unwrap_or("");
+    Some(entry(level, timestamp, message))
+}
+
+/// `<timestamp> | LEVEL | module | message`.
+fn parse_engine_text(line: &str) -> Option<LogEntry> {
+    let mut fields = line.splitn(4, " | ");
+    let timestamp = fields.next()?.trim();
+    if !timestamp.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let level = normalize_level(fields.next()?)?;
+    let module = fields.next()?.trim();
+    let message = fields.next().unwrap_or("");
+    Some(entry(
+        level,
+        Some(timestamp.to_string()),
+        &format!("{}: {}", module, message),
+    ))
+}
+
+/// `INFO:     message`.
+fn parse_uvicorn(line: &str) -> Option<LogEntry> {
+    let (level, message) = line.split_once(':')?;
+    if level.is_empty() || level.chars().any(|c| !c.is_ascii_uppercase()) {
+        return None;
+    }
+    Some(entry(normalize_level(level)?, None, message.trim_start()))
+}
+
+fn parse_launcher(line: &str) -> Option<LogEntry> {
+    let message = line.strip_prefix("[SOLAT] ")?;
+    let level = if message.starts_with("WARNING:") {
+        "warning"
+    } else if message.contains("Failed") || message.contains("failed") {
+        "error"
+    } else {
+        "info"
+    };
+    Some(entry(level, None, message))
+}
+
+/// The record `line` starts, or `raw` if it matches no known format.
+pub fn parse_line(line: &str) -> LogEntry {
+    parse_json(line)
+        .or_else(|| parse_engine_text(line))
+        .or_else(|| parse_uvicorn(line))
+        .or_else(|| parse_launcher(line))
+        .unwrap_or_else(|| entry(RAW_LEVEL, None, line))
+}
+
+/// Group raw log lines into records, folding tracebacks and other
+/// continuation lines into the record they belong to.
+pub fn parse_entries<'a>(lines: impl IntoIterator<Item = &'a str>) -> Vec<LogEntry> {
+    let mut entries: Vec<LogEntry> = Vec::new();
+    for line in lines {
+        if line.is_empty() && entries.is_empty() {
+            continue;
+        }
+        let starts_traceback = line.starts_with("Traceback (most recent call last)");
+        let continues = line.is_empty() || is_continuation(line);
+
+        match entries.last_mut() {
+            // A traceback under an ordinary record gets a record of its own,
+            // so the UI can collapse it
+            Some(last) if continues && starts_traceback && !last.is_traceback => {
+                let level = if last.level == "critical" {
+                    "critical"
+                } else {
+                    "error"
+                };
+                let traceback = LogEntry {
+                    is_traceback: true,
+                    ..entry(level, last.timestamp.clone(), line)
+                };
+                entries.push(traceback);
+            }
+            Some(last) if continues => {
+                last.message.push('\n');
+                last.message.push_str(line);
+            }
+            // The tail began mid-record: keep the orphaned lines as raw
+            None if continues => entries.push(LogEntry {
+                is_traceback: true,
+                ..entry(RAW_LEVEL, None, line)
+            }),
+            _ => entries.push(parse_line(line)),
+        }
+    }
+    // Blank separators may trail a record; they aren't part of it
+    for entry in &mut entries {
+        let trimmed = entry.message.trim_end_matches('\n').len();
+        entry.message.truncate(trimmed);
+    }
+    entries
+}
+
+/// Record counts by level, for an "N errors, M warnings" overview.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub struct LevelCounts {
+    /// `error` and `critical` records.
+    pub error: usize,
+    pub warning: usize,
+    pub info: usize,
+    pub debug: usize,
+    /// Lines in no recognised format.
+    pub other: usize,
+}
+
+/// Tally `entries` by level. A traceback record counts as an error only on
+/// its own: under an error that logged it, it's the same failure.
+pub fn count_levels(entries: &[LogEntry]) -> LevelCounts {
+    let mut counts = LevelCounts::default();
+    let mut previous: Option<&LogEntry> = None;
+    for entry in entries {
+        let logged_above = previous.is_some_and(|p| p.level == "error" || p.level == "critical");
+        if !(entry.is_traceback && logged_above) {
+            match entry.level.as_str() {
+                "error" | "critical" => counts.error += 1,
+                "warning" => counts.warning += 1,
+                "info" => counts.info += 1,
+                "debug" => counts.debug += 1,
+                _ => counts.other += 1,
+            }
+        }
+        previous = Some(entry);
+    }
+    counts
+}
@@ -0,0 +1,250 @@
+//! Live streaming of the engine boot log to the frontend.
+//!
+//! A tailer thread follows the active log file and pushes complete lines
+//! into a bounded channel; a drain thread coalesces whatever has arrived
+//! into one `Vec<String>` event every `BATCH_INTERVAL_MS`. When the frontend
+//! can't keep up the channel fills, excess lines are counted rather than
+//! queued, and the next batch carries an "N lines dropped" marker instead.
+//!
+//! Quiet stretches also emit each line on its own as `engine://log-line`
+//! with the time it was read, for consoles that render line by line. A
+//! batch bigger than `LINE_EVENTS_PER_BATCH` would bring back the per-line
+//! IPC flood the batching avoids, so it gets one marker line there instead
+//! and its lines only go out in the batch. The stream starts with the
+//! engine, so output is live from the first line.
+
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TryRecvError, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::sync::LockOrRecover;
+use crate::{logs, EngineLogPath};
+
+const LOG_LINES_EVENT: &str = "engine://log-lines";
+const LOG_LINE_EVENT: &str = "engine://log-line";
+/// Lines buffered between tailer and drain before new ones are dropped.
+const CHANNEL_CAPACITY: usize = 1000;
+const BATCH_INTERVAL_MS: u64 = 100;
+/// Most `engine://log-line` events sent per batch.
+const LINE_EVENTS_PER_BATCH: usize = 20;
+const POLL_INTERVAL_MS: u64 = 100;
+
+/// Stop flag of the running streamer, if any.
+pub struct LogStreamState(pub Mutex<Option<Arc<AtomicBool>>>);
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct LogLine {
+    line: String,
+    /// When the tailer read the line, in Unix milliseconds.
+    timestamp: u64,
+}
+
+impl LogLine {
+    fn now(line: String) -> Self {
+        Self {
+            line,
+            timestamp: chrono::Utc::now().timestamp_millis().max(0) as u64,
+        }
+    }
+}
+
+/// Start streaming log lines, from the end of the current log if `from_end`
+/// (only new output) or else from its first line. Returns false if a stream
+/// is already running.
+pub fn start(app: AppHandle, state: &LogStreamState, from_end: bool) -> Result<bool, String> {
+    let mut running = state.0.lock_or_recover();
+    if running.is_some() {
+        return Ok(false);
+    }
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let dropped = Arc::new(AtomicUsize::new(0));
+    let (tx, rx) = mpsc::sync_channel(CHANNEL_CAPACITY);
+
+    let tailer_app = app.clone();
+    let tailer_stop = stop.clone();
+    let tailer_dropped = dropped.clone();
+    std::thread::spawn(move || run_tailer(tailer_app, tx, tailer_dropped, tailer_stop, from_end));
+    std::thread::spawn(move || run_drain(app, rx, dropped));
+
+    *running = Some(stop);
+    Ok(true)
+}
+
+/// Stop the running stream. Returns false if none was running.
+pub fn stop(state: &LogStreamState) -> Result<bool, String> {
+    let mut running = state.0.lock_or_recover();
+    match running.take() {
+        Some(stop) => {
+            stop.store(true, Ordering::Relaxed);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+struct TailedFile {
+    path: PathBuf,
+    reader: BufReader<File>,
+    offset: u64,
+    identity: Option<u64>,
+}
+
+/// What identifies the file behind a path: its inode, so a log renamed away
+/// and recreated is noticed even when the new one has already outgrown the
+/// read offset.
+#[cfg(unix)]
+fn file_identity(meta: &fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(meta.ino())
+}
+
+/// No stable file id without extra APIs; truncation is still caught by size.
+#[cfg(windows)]
+fn file_identity(_meta: &fs::Metadata) -> Option<u64> {
+    None
+}
+
+impl TailedFile {
+    fn open(path: PathBuf, from_end: bool) -> Option<Self> {
+        let mut file = File::open(&path).ok()?;
+        let identity = file.metadata().ok().as_ref().and_then(file_identity);
+        let offset = if from_end {
+            file.seek(SeekFrom::End(0)).ok()?
+        } else {
+            0
+        };
+        Some(Self {
+            path,
+            reader: BufReader::new(file),
+            offset,
+            identity,
+        })
+    }
+
+    /// Whether the path now holds a different or truncated file.
+    fn replaced(&self) -> bool {
+        match fs::metadata(&self.path) {
+            Ok(meta) => meta.len() < self.offset || file_identity(&meta) != self.identity,
+            Err(_) => true,
+        }
+    }
+}
+
+fn run_tailer(
+    app: AppHandle,
+    tx: SyncSender<LogLine>,
+    dropped: Arc<AtomicUsize>,
+    stop: Arc<AtomicBool>,
+    from_end: bool,
+) {
+    let mut tailed: Option<TailedFile> = None;
+    let mut first_open = from_end;
+    let mut pending: Vec<u8> = Vec::new();
+
+    while !stop.load(Ordering::Relaxed) {
+        let path = app.state::<EngineLogPath>().0.lock_or_recover().clone();
+
+        // (Re)open when the log path changes, or a new spawn replaced or
+        // truncated the file. Only a `from_end` first open skips existing
+        // content; after that we want the new file from the top.
+        if tailed
+            .as_ref()
+            .is_some_and(|t| t.path != path || t.replaced())
+        {
+            tailed = None;
+            pending.clear();
+        }
+        if tailed.is_none() {
+            tailed = TailedFile::open(path, first_open);
+            if tailed.is_some() {
+                first_open = false;
+            }
+        }
+
+        if let Some(t) = tailed.as_mut() {
+            loop {
+                // Bytes rather than read_line: engine output isn't guaranteed
+                // to be valid UTF-8
+                match t.reader.read_until(b'\n', &mut pending) {
+                    Ok(0) => break,
+                    Ok(n) => {
+                        t.offset += n as u64;
+                        // A line without its newline is still being written
+                        if !pending.ends_with(b"\n") {
+                            break;
+                        }
+                        let line = logs::sanitize_log_bytes(&pending)
+                            .trim_end_matches(['\r', '\n'])
+                            .to_string();
+                        pending.clear();
+                        match tx.try_send(LogLine::now(line)) {
+                            Ok(()) => {}
+                            Err(TrySendError::Full(_)) => {
+                                dropped.fetch_add(1, Ordering::Relaxed);
+                            }
+                            Err(TrySendError::Disconnected(_)) => return,
+                        }
+                    }
+                    // Transient read error: retry from the same spot next poll
+                    Err(_) => break,
+                }
+            }
+        }
+
+        std::thread::sleep(Duration::from_millis(POLL_INTERVAL_MS));
+    }
+}
+
+fn run_drain(app: AppHandle, rx: Receiver<LogLine>, dropped: Arc<AtomicUsize>) {
+    loop {
+        std::thread::sleep(Duration::from_millis(BATCH_INTERVAL_MS));
+
+        let mut batch = Vec::new();
+        let mut finished = false;
+        loop {
+            match rx.try_recv() {
+                Ok(line) => batch.push(line),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    finished = true;
+                    break;
+                }
+            }
+        }
+
+        let lost = dropped.swap(0, Ordering::Relaxed);
+        if lost > 0 {
+            batch.push(LogLine::now(format!(
+                "[SOLAT] ... {} log lines dropped (output faster than the UI can render) ...",
+                lost
+            )));
+        }
+        if !batch.is_empty() {
+            if batch.len() <= LINE_EVENTS_PER_BATCH {
+                for line in &batch {
+                    let _ = app.emit(LOG_LINE_EVENT, line);
+                }
+            } else {
+                let marker = LogLine::now(format!(
+                    "[SOLAT] ... {} lines arrived at once; see engine://log-lines for them ...",
+                    batch.len()
+                ));
+                let _ = app.emit(LOG_LINE_EVENT, &marker);
+            }
+            let lines: Vec<&str> = batch.iter().map(|line| line.line.as_str()).collect();
+            if let Err(e) = app.emit(LOG_LINES_EVENT, &lines) {
+                eprintln!("[SOLAT] WARNING: Failed to emit log lines: {}", e);
+            }
+        }
+        if finished {
+            return;
+        }
+    }
+}
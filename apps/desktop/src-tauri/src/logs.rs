@@ -0,0 +1,763 @@
+//! Engine boot log files: location, daily rotation, retention, size cap and
+//! tailing.
+
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+use chrono::{Local, NaiveDate};
+
+use crate::log_parse;
+use crate::sync::LockOrRecover;
+
+const ACTIVE_LOG_NAME: &str = "engine-boot.log";
+/// The previous boot's log, kept when a spawn starts a fresh one.
+const PREVIOUS_LOG_NAME: &str = "engine-boot.prev.log";
+const DAILY_LOG_PREFIX: &str = "engine-boot.";
+const DAILY_LOG_SUFFIX: &str = ".log";
+/// Hitting the cap again this soon after a rotation means the engine is
+/// logging faster than copies are worth keeping; truncate instead.
+const MIN_ROTATION_INTERVAL_SECS: u64 = 10;
+/// A boot log bigger than this when the next spawn starts is kept as
+/// `<name>.1` rather than truncated.
+pub const BOOT_LOG_ROTATE_BYTES: u64 = 10 * 1024 * 1024;
+/// Rotated boot logs kept (`<name>.1` newest to `<name>.5` oldest).
+const BOOT_LOG_KEEP: u32 = 5;
+/// Lines `get_engine_log` returns unless asked for another count.
+pub const ENGINE_LOG_LINES: usize = 100;
+/// Bytes read per step when scanning a log backwards for its tail.
+const TAIL_CHUNK_BYTES: u64 = 64 * 1024;
+
+/// Last `summarize_log` result, keyed by path, length and mtime.
+static SUMMARY_CACHE: Mutex<Option<(PathBuf, u64, SystemTime, log_parse::LevelCounts)>> =
+    Mutex::new(None);
+
+pub fn engine_log_path(data_dir: &Path) -> PathBuf {
+    let log_dir = data_dir.join("logs");
+    let _ = fs::create_dir_all(&log_dir);
+    log_dir.join(ACTIVE_LOG_NAME)
+}
+
+/// Truncate (or create) the log at `path` and open it for appending.
+///
+/// Append mode matters for the size cap: the engine's writes then always land
+/// at the current end, so truncating the file underneath it doesn't leave a
+/// hole of zeros up to its old offset.
+pub fn create_log(path: &Path) -> io::Result<fs::File> {
+    fs::File::create(path)?;
+    fs::OpenOptions::new().append(true).open(path)
+}
+
+/// Empty the log at `path` in place, returning how many bytes it held. A
+/// missing log clears nothing.
+///
+/// The engine's append-mode handle keeps writing at the new end on Unix. On
+/// Windows the truncation fails if another program opened the file without
+/// write sharing (an editor or virus scanner, say); that surfaces as an I/O
+/// error, and clearing after the engine stops works.
+pub fn clear_log(path: &Path) -> Result<u64, LogError> {
+    let file = match fs::OpenOptions::new().write(true).open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+    let len = file.metadata()?.len();
+    file.set_len(0)?;
+    Ok(len)
+}
+
+/// `<name>.<n>` alongside `path`.
+fn numbered_log_path(path: &Path, n: u32) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}", n));
+    path.with_file_name(name)
+}
+
+/// Free up `<name>.1` by shifting each kept copy one number up, dropping
+/// the oldest.
+fn shift_numbered_logs(path: &Path) {
+    let _ = fs::remove_file(numbered_log_path(path, BOOT_LOG_KEEP));
+    for n in (1..BOOT_LOG_KEEP).rev() {
+        let from = numbered_log_path(path, n);
+        if from.exists() {
+            let _ = fs::rename(&from, numbered_log_path(path, n + 1));
+        }
+    }
+}
+
+/// Before a spawn truncates the log at `path`, shift it to `<name>.1` (and
+/// `.1` to `.2`, up to `BOOT_LOG_KEEP`) if it has grown past `max_bytes`, so
+/// a long session's output survives the next boot. Returns whether it
+/// rotated. A daily-log alias is left alone; those files are never truncated.
+pub fn rotate_log_if_needed(path: &Path, max_bytes: u64) -> bool {
+    match fs::symlink_metadata(path) {
+        Ok(meta) if meta.is_file() && meta.len() > max_bytes => {}
+        _ => return false,
+    }
+    shift_numbered_logs(path);
+    let rotated = numbered_log_path(path, 1);
+    match fs::rename(path, &rotated) {
+        Ok(()) => {
+            println!("[SOLAT] Rotated boot log to {}", rotated.display());
+            // `<name>.1` is the previous boot's log now; don't leave an
+            // older one posing as it
+            let _ = fs::remove_file(path.with_file_name(PREVIOUS_LOG_NAME));
+            true
+        }
+        Err(e) => {
+            eprintln!(
+                "[SOLAT] WARNING: Failed to rotate {}: {}",
+                path.display(),
+                e
+            );
+            false
+        }
+    }
+}
+
+/// Before a spawn truncates the log at `path`, move it to
+/// `engine-boot.prev.log` (replacing the one before), so a crash's output
+/// survives the restart that follows it. Daily-log aliases are left alone.
+pub fn keep_previous_log(path: &Path) {
+    if !fs::symlink_metadata(path).is_ok_and(|meta| meta.is_file()) {
+        return;
+    }
+    let previous = path.with_file_name(PREVIOUS_LOG_NAME);
+    if let Err(e) = fs::rename(path, &previous) {
+        eprintln!(
+            "[SOLAT] WARNING: Failed to keep previous log as {}: {}",
+            previous.display(),
+            e
+        );
+    }
+}
+
+/// The previous boot's log next to the active log at `active_path`: the
+/// kept copy, or the newest numbered one if that boot's log was rotated.
+pub fn previous_log_path(active_path: &Path) -> Option<PathBuf> {
+    let previous = active_path.with_file_name(PREVIOUS_LOG_NAME);
+    if previous.is_file() {
+        return Some(previous);
+    }
+    Some(numbered_log_path(active_path, 1)).filter(|path| path.is_file())
+}
+
+/// `read_engine_log` for the previous boot's log, reading "(no previous
+/// log)" when there is none yet.
+pub fn read_previous_log(active_path: &Path) -> Result<EngineLog, LogError> {
+    match previous_log_path(active_path) {
+        Some(path) => read_engine_log(&path, ENGINE_LOG_LINES),
+        None => Ok(EngineLog {
+            content: "(no previous log)".to_string(),
+            log_exists: false,
+        }),
+    }
+}
+
+/// `engine-boot.YYYY-MM-DD.log` alongside the active log.
+fn daily_log_path(log_dir: &Path, date: NaiveDate) -> PathBuf {
+    log_dir.join(format!(
+        "{}{}{}",
+        DAILY_LOG_PREFIX,
+        date.format("%Y-%m-%d"),
+        DAILY_LOG_SUFFIX
+    ))
+}
+
+/// Parse the date out of a daily log file name, or a `rotate_now` archive's
+/// (`engine-boot.YYYY-MM-DD-HHMMSS.log`); `None` for any other file.
+fn daily_log_date(file_name: &str) -> Option<NaiveDate> {
+    let stem = file_name
+        .strip_prefix(DAILY_LOG_PREFIX)?
+        .strip_suffix(DAILY_LOG_SUFFIX)?;
+    let (date, rest) = stem.split_at_checked(10)?;
+    if !rest.is_empty() && !rest.starts_with('-') {
+        return None;
+    }
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+/// Open today's dated log for appending and point the active log alias at it,
+/// so the tailing code can keep reading `engine-boot.log`.
+///
+/// The day is fixed at spawn time: a session that runs past midnight keeps
+/// writing to the file it started in, since the child's stdout can't be
+/// reopened underneath it.
+pub fn open_daily_log(active_path: &Path) -> Result<fs::File, String> {
+    let log_dir = active_path.parent().unwrap_or_else(|| Path::new("."));
+    let today_path = daily_log_path(log_dir, Local::now().date_naive());
+
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&today_path)
+        .map_err(|e| format!("Failed to open daily log file: {}", e))?;
+
+    // Replace whatever is at the alias path (a previous symlink or a plain
+    // log from before daily rotation was enabled).
+    if fs::symlink_metadata(active_path).is_ok() {
+        let _ = fs::remove_file(active_path);
+    }
+    if let Err(e) = link_active_log(&today_path, active_path) {
+        eprintln!(
+            "[SOLAT] WARNING: Failed to link {} to {}: {}",
+            active_path.display(),
+            today_path.display(),
+            e
+        );
+    }
+
+    Ok(file)
+}
+
+#[cfg(unix)]
+fn link_active_log(target: &Path, alias: &Path) -> std::io::Result<()> {
+    // Relative target so the log dir can be moved as a whole
+    let target_name = target.file_name().map(PathBuf::from).unwrap_or_default();
+    std::os::unix::fs::symlink(target_name, alias)
+}
+
+#[cfg(windows)]
+fn link_active_log(target: &Path, alias: &Path) -> std::io::Result<()> {
+    // Symlinks need elevated privileges on Windows; a hard link needs none and
+    // still shows everything appended through either name.
+    fs::hard_link(target, alias)
+}
+
+/// Delete dated logs older than `days` days, returning how many were removed.
+/// Only `engine-boot.YYYY-MM-DD.log` files and `rotate_now` archives are
+/// considered.
+pub fn prune_old_logs(log_dir: &Path, days: u32) -> usize {
+    let Some(cutoff) = Local::now()
+        .date_naive()
+        .checked_sub_days(chrono::Days::new(days as u64))
+    else {
+        return 0;
+    };
+
+    let Ok(entries) = fs::read_dir(log_dir) else {
+        return 0;
+    };
+
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let Some(date) = daily_log_date(&name.to_string_lossy()) else {
+            continue;
+        };
+        if date < cutoff && fs::remove_file(entry.path()).is_ok() {
+            removed += 1;
+        }
+    }
+    if removed > 0 {
+        println!(
+            "[SOLAT] Pruned {} log file(s) older than {} days",
+            removed, days
+        );
+    }
+    removed
+}
+
+/// Archive the current log as `engine-boot.<YYYY-MM-DD-HHMMSS>.log` (a name
+/// `prune_old_logs` ages out) and leave an empty active log, returning the
+/// archived path. With daily rotation the dated file behind the alias is the
+/// one archived.
+///
+/// While the engine runs it holds the file open, so the log is copied and
+/// then truncated in place, as `enforce_size_cap` does, and its output keeps
+/// going to the active log; lines written during the copy are lost.
+/// Otherwise the file is simply moved aside.
+pub fn rotate_now(active_path: &Path, engine_running: bool) -> Result<PathBuf, LogError> {
+    let target = fs::canonicalize(active_path)?;
+    let log_dir = target.parent().unwrap_or_else(|| Path::new("."));
+    let stamp = Local::now().format("%Y-%m-%d-%H%M%S").to_string();
+
+    let mut archived = log_dir.join(format!("{}{}{}", DAILY_LOG_PREFIX, stamp, DAILY_LOG_SUFFIX));
+    let mut n = 1;
+    while archived.exists() {
+        archived = log_dir.join(format!(
+            "{}{}-{}{}",
+            DAILY_LOG_PREFIX, stamp, n, DAILY_LOG_SUFFIX
+        ));
+        n += 1;
+    }
+    if engine_running {
+        fs::copy(&target, &archived)?;
+        fs::OpenOptions::new()
+            .write(true)
+            .open(&target)?
+            .set_len(0)?;
+    } else {
+        fs::rename(&target, &archived)?;
+        // Drop the daily alias too (now dangling), so the fresh log is a
+        // plain file
+        if fs::symlink_metadata(active_path).is_ok() {
+            let _ = fs::remove_file(active_path);
+        }
+        fs::File::create(active_path)?;
+    }
+    println!("[SOLAT] Rotated log to {}", archived.display());
+    Ok(archived)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CapAction {
+    Rotated,
+    Truncated,
+}
+
+/// Enforce `max_bytes` on the log at `path` (following the daily alias to
+/// the file actually being written).
+///
+/// The engine holds the file open and can't be told to reopen it, so
+/// rotation is copy-then-truncate into `<name>.1` (shifting older copies up,
+/// as at spawn); lines written during the copy are lost. If the copy fails
+/// (e.g. the disk is already full) or the previous rotation was under
+/// `MIN_ROTATION_INTERVAL_SECS` ago, the log is just truncated. Either way
+/// a marker line records what happened.
+pub fn enforce_size_cap(
+    path: &Path,
+    max_bytes: u64,
+    last_rotation: &mut Option<Instant>,
+) -> Option<CapAction> {
+    let len = fs::metadata(path).ok()?.len();
+    if len <= max_bytes {
+        return None;
+    }
+    let target = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+
+    let rotated = if last_rotation
+        .is_some_and(|at| at.elapsed() < Duration::from_secs(MIN_ROTATION_INTERVAL_SECS))
+    {
+        Err("engine is logging faster than rotation can keep up".to_string())
+    } else {
+        shift_numbered_logs(&target);
+        let copy_path = numbered_log_path(&target, 1);
+        fs::copy(&target, &copy_path)
+            .map(|_| copy_path)
+            .map_err(|e| format!("rotation failed: {}", e))
+    };
+
+    if let Err(e) = fs::OpenOptions::new()
+        .write(true)
+        .open(&target)
+        .and_then(|file| file.set_len(0))
+    {
+        eprintln!(
+            "[SOLAT] WARNING: Log {} is over {} bytes but could not be truncated: {}",
+            target.display(),
+            max_bytes,
+            e
+        );
+        return None;
+    }
+
+    let (action, marker) = match rotated {
+        Ok(copy_path) => {
+            *last_rotation = Some(Instant::now());
+            println!(
+                "[SOLAT] Log reached {} bytes, rotated to {}",
+                len,
+                copy_path.display()
+            );
+            (
+                CapAction::Rotated,
+                format!(
+                    "[SOLAT] Log reached {} bytes (cap {}), previous output moved to {}",
+                    len,
+                    max_bytes,
+                    copy_path.display()
+                ),
+            )
+        }
+        Err(reason) => {
+            eprintln!(
+                "[SOLAT] WARNING: Log reached {} bytes, truncated ({})",
+                len, reason
+            );
+            (
+                CapAction::Truncated,
+                format!(
+                    "[SOLAT] WARNING: Log truncated at {} bytes (cap {}): {}",
+                    len, max_bytes, reason
+                ),
+            )
+        }
+    };
+    if let Ok(mut file) = fs::OpenOptions::new().append(true).open(&target) {
+        let _ = writeln!(file, "{}", marker);
+    }
+    Some(action)
+}
+
+/// Log bytes as text with NUL bytes dropped. An interrupted write (or a
+/// truncation under a non-append writer) leaves runs of NULs that would
+/// otherwise show up as garbage; invalid UTF-8 is replaced, not an error.
+pub fn sanitize_log_bytes(bytes: &[u8]) -> String {
+    let cleaned: Vec<u8> = bytes.iter().copied().filter(|b| *b != 0).collect();
+    String::from_utf8_lossy(&cleaned).into_owned()
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct LogHealth {
+    pub readable: bool,
+    /// Last newline-terminated line, NULs removed.
+    pub last_complete_line: Option<String>,
+    /// The file ends mid-line (or in NUL padding), as after a crash mid-write.
+    pub truncated: bool,
+    pub nul_bytes: usize,
+    /// Why the log couldn't be read, when `readable` is false.
+    pub error: Option<String>,
+}
+
+/// Level counts over the whole log at `path`, rescanned only when its length
+/// or mtime has changed since the last call. A missing log counts as empty.
+pub fn summarize_log(path: &Path) -> Result<log_parse::LevelCounts, LogError> {
+    let meta = match fs::metadata(path) {
+        Ok(meta) => meta,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Default::default()),
+        Err(e) => return Err(e.into()),
+    };
+    let (len, modified) = (meta.len(), meta.modified()?);
+    let mut cache = SUMMARY_CACHE.lock_or_recover();
+    if let Some((cached_path, cached_len, cached_modified, counts)) = cache.as_ref() {
+        if cached_path == path && *cached_len == len && *cached_modified == modified {
+            return Ok(*counts);
+        }
+    }
+
+    let content = sanitize_log_bytes(&fs::read(path)?);
+    let counts = log_parse::count_levels(&log_parse::parse_entries(content.lines()));
+    *cache = Some((path.to_path_buf(), len, modified, counts));
+    Ok(counts)
+}
+
+/// Check the log at `path` for signs of an incomplete or corrupted write.
+pub fn verify_log(path: &Path) -> LogHealth {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            return LogHealth {
+                readable: false,
+                last_complete_line: None,
+                truncated: false,
+                nul_bytes: 0,
+                error: Some(e.to_string()),
+            }
+        }
+    };
+
+    let nul_bytes = bytes.iter().filter(|b| **b == 0).count();
+    let truncated = bytes.last().is_some_and(|b| *b != b'\n');
+    let last_complete_line = bytes
+        .iter()
+        .rposition(|b| *b == b'\n')
+        .map(|end| &bytes[..end])
+        .map(|complete| {
+            let start = complete
+                .iter()
+                .rposition(|b| *b == b'\n')
+                .map_or(0, |i| i + 1);
+            sanitize_log_bytes(&complete[start..])
+                .trim_end_matches('\r')
+                .to_string()
+        });
+
+    LogHealth {
+        readable: true,
+        last_complete_line,
+        truncated,
+        nul_bytes,
+        error: None,
+    }
+}
+
+pub fn read_log_tail(path: &Path, lines: usize) -> String {
+    read_tail(path, lines).unwrap_or_else(|_| String::from("(no log file found)"))
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct EngineLog {
+    pub content: String,
+    /// False before the engine has ever written a log, which is expected on
+    /// first launch rather than an error.
+    pub log_exists: bool,
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum LogError {
+    Io { kind: String, message: String },
+}
+
+impl From<io::Error> for LogError {
+    fn from(e: io::Error) -> Self {
+        LogError::Io {
+            kind: format!("{:?}", e.kind()),
+            message: e.to_string(),
+        }
+    }
+}
+
+/// Every line of the log at `path` mentioning `request_id`, each followed by
+/// its continuation lines (e.g. the traceback of an error logged with that
+/// id). A missing log yields no lines; so does a blank id, which would
+/// otherwise match everything.
+pub fn lines_for_request(path: &Path, request_id: &str) -> Result<Vec<String>, LogError> {
+    let request_id = request_id.trim();
+    if request_id.is_empty() {
+        return Ok(Vec::new());
+    }
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let content = sanitize_log_bytes(&bytes);
+
+    let mut matched = Vec::new();
+    let mut in_block = false;
+    for line in content.lines() {
+        if line.contains(request_id) {
+            matched.push(line.to_string());
+            in_block = true;
+        } else if in_block && (line.is_empty() || log_parse::is_continuation(line)) {
+            matched.push(line.to_string());
+        } else {
+            in_block = false;
+        }
+    }
+    // Blank lines only separate chained tracebacks; they carry nothing on their own
+    matched.retain(|line| !line.is_empty());
+    Ok(matched)
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct LogMatch {
+    /// 1-based.
+    pub line_number: usize,
+    pub line: String,
+}
+
+/// The first `max_matches` lines of `path` containing `query`, read a line
+/// at a time so the log never has to fit in memory. A missing log, or a
+/// blank query (which would match everything), yields none.
+pub fn search_log(
+    path: &Path,
+    query: &str,
+    case_insensitive: bool,
+    max_matches: usize,
+) -> Result<Vec<LogMatch>, LogError> {
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let query = if case_insensitive {
+        query.to_lowercase()
+    } else {
+        query.to_string()
+    };
+
+    let mut reader = BufReader::new(file);
+    let mut buf = Vec::new();
+    let mut matches = Vec::new();
+    let mut line_number = 0;
+    while matches.len() < max_matches {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf)? == 0 {
+            break;
+        }
+        line_number += 1;
+        let line = sanitize_log_bytes(&buf);
+        let line = line.trim_end_matches(['\n', '\r']);
+        let found = if case_insensitive {
+            line.to_lowercase().contains(&query)
+        } else {
+            line.contains(&query)
+        };
+        if found {
+            matches.push(LogMatch {
+                line_number,
+                line: line.to_string(),
+            });
+        }
+    }
+    Ok(matches)
+}
+
+/// Last `lines` lines of `path`, with a missing file read as empty.
+pub fn read_tail_lines(path: &Path, lines: usize) -> Result<Vec<String>, LogError> {
+    match tail_lines(path, lines) {
+        Ok(tail) => Ok(tail),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Last `lines` lines of `path`, treating a missing file as an empty log.
+pub fn read_engine_log(path: &Path, lines: usize) -> Result<EngineLog, LogError> {
+    match read_tail(path, lines) {
+        Ok(content) => Ok(EngineLog {
+            content,
+            log_exists: true,
+        }),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(EngineLog {
+            content: String::new(),
+            log_exists: false,
+        }),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Offset in `file` (of length `len`) where its last `lines` lines begin,
+/// found by scanning backwards in chunks rather than reading it all.
+fn tail_start(file: &mut File, len: u64, lines: usize) -> io::Result<u64> {
+    if lines == 0 {
+        return Ok(len);
+    }
+    let mut buf = vec![0; TAIL_CHUNK_BYTES as usize];
+    let mut found = 0;
+    // Until something other than NUL padding has been passed, a newline is
+    // the one ending the last line rather than one starting it
+    let mut past_end = false;
+    let mut end = len;
+    while end > 0 {
+        let start = end.saturating_sub(TAIL_CHUNK_BYTES);
+        let chunk = &mut buf[..(end - start) as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(chunk)?;
+        for (i, byte) in chunk.iter().enumerate().rev() {
+            match byte {
+                0 => {}
+                b'\n' if past_end => {
+                    found += 1;
+                    if found == lines {
+                        return Ok(start + i as u64 + 1);
+                    }
+                }
+                _ => past_end = true,
+            }
+        }
+        end = start;
+    }
+    Ok(0)
+}
+
+/// Last `lines` lines of `path`, NULs removed, reading only the end of the
+/// file: the same lines as taking the tail of the whole sanitized log.
+fn tail_lines(path: &Path, lines: usize) -> io::Result<Vec<String>> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let start = tail_start(&mut file, len, lines)?;
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    let content = sanitize_log_bytes(&bytes);
+    // The file may have grown since `len` was read
+    let all_lines: Vec<&str> = content.lines().collect();
+    let first = all_lines.len().saturating_sub(lines);
+    Ok(all_lines[first..].iter().map(|l| l.to_string()).collect())
+}
+
+/// `tail_lines` joined by `\n`.
+fn read_tail(path: &Path, lines: usize) -> io::Result<String> {
+    tail_lines(path, lines).map(|tail| tail.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The tail taken the slow way: sanitize the whole log, then split it.
+    fn naive_tail(bytes: &[u8], lines: usize) -> Vec<String> {
+        let content = sanitize_log_bytes(bytes);
+        let all_lines: Vec<&str> = content.lines().collect();
+        let first = all_lines.len().saturating_sub(lines);
+        all_lines[first..].iter().map(|l| l.to_string()).collect()
+    }
+
+    fn assert_tail_matches_naive(name: &str, bytes: &[u8]) {
+        let path =
+            std::env::temp_dir().join(format!("solat-tail-{}-{}.log", std::process::id(), name));
+        fs::write(&path, bytes).unwrap();
+        for lines in [0, 1, 2, 3, 10, 500, 20_000, 1_000_000] {
+            assert_eq!(
+                tail_lines(&path, lines).unwrap(),
+                naive_tail(bytes, lines),
+                "{}: last {} lines",
+                name,
+                lines
+            );
+        }
+        let _ = fs::remove_file(&path);
+    }
+
+    /// Several MB of lines of varied length, some longer than a chunk, so
+    /// newlines land on and around chunk boundaries.
+    fn generated_log(line_ending: &str) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut state: u64 = 0x5eed;
+        let mut i = 0;
+        while bytes.len() < 3 * 1024 * 1024 {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let width = match state >> 60 {
+                0 => 0,
+                1 => TAIL_CHUNK_BYTES as usize + 17,
+                _ => (state >> 32) as usize % 300,
+            };
+            bytes.extend_from_slice(format!("{:08} ", i).as_bytes());
+            bytes.resize(bytes.len() + width, b'a' + (i % 26) as u8);
+            if state & 0xff == 0 {
+                bytes.extend_from_slice(&[0, 0, 0]);
+            }
+            bytes.extend_from_slice(line_ending.as_bytes());
+            i += 1;
+        }
+        bytes
+    }
+
+    #[test]
+    fn tail_matches_naive_tail_of_large_log() {
+        assert_tail_matches_naive("lf", &generated_log("\n"));
+    }
+
+    #[test]
+    fn tail_matches_naive_tail_without_trailing_newline() {
+        let mut bytes = generated_log("\n");
+        bytes.extend_from_slice(b"partial last line");
+        assert_tail_matches_naive("no-newline", &bytes);
+    }
+
+    #[test]
+    fn tail_matches_naive_tail_with_crlf() {
+        assert_tail_matches_naive("crlf", &generated_log("\r\n"));
+    }
+
+    #[test]
+    fn tail_matches_naive_tail_with_nul_padding() {
+        // As left by a crash mid-write: a NUL-padded, possibly cut-off end
+        let mut bytes = generated_log("\n");
+        bytes.resize(bytes.len() + TAIL_CHUNK_BYTES as usize * 2 + 5, 0);
+        assert_tail_matches_naive("nul-padding", &bytes);
+        let mut bytes = generated_log("\n");
+        bytes.extend_from_slice(b"cut off\0\0\0\n\0\0");
+        assert_tail_matches_naive("nul-mid", &bytes);
+    }
+
+    #[test]
+    fn tail_matches_naive_tail_of_small_logs() {
+        assert_tail_matches_naive("empty", b"");
+        assert_tail_matches_naive("one-line", b"only line\n");
+        assert_tail_matches_naive("short", b"first\nsecond\r\n\nfourth");
+        assert_tail_matches_naive("blank-lines", b"\n\n\n");
+        assert_tail_matches_naive("all-nul", &[0; 64]);
+    }
+}
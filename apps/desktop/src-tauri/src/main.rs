@@ -1,75 +1,576 @@
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use std::fs;
-use std::io::{BufRead, BufReader};
-use std::net::TcpStream;
-use std::path::PathBuf;
-use std::process::{Child, Command as StdCommand, Stdio};
-use std::sync::Mutex;
+use std::io::Write;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command as StdCommand, ExitStatus, Stdio};
+use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant};
-use tauri::Manager;
+use tauri::{AppHandle, Emitter, Manager, RunEvent};
+
+mod canary;
+mod compat;
+mod config;
+mod diagnostics;
+mod error;
+mod export;
+mod health;
+mod hooks;
+mod http;
+mod log_parse;
+mod log_stream;
+mod logs;
+mod memory_log;
+mod model;
+mod monitor;
+mod process;
+mod profiling;
+mod schedule;
+mod supervisor;
+mod sync;
+mod telemetry;
+mod venv;
+
+use canary::DeepHealthState;
+use config::{ConfigError, EngineConfig, LogTarget, PortSetting};
+use error::EngineError;
+use log_stream::LogStreamState;
+use memory_log::{Echo, MemoryLog};
+use model::ActiveModel;
+use profiling::ProfileOutput;
+use supervisor::{EngineMetrics, EngineStats, RestartBudget, RestartHistory, RestartRecord};
+use sync::LockOrRecover;
+use telemetry::TelemetryEvent;
 
 struct EngineProcess(Mutex<Option<Child>>);
 
+/// The lifecycle operation (a stop, or any launch: manual, watchdog,
+/// scheduled, ...) in progress, if any. Held for the whole operation so a
+/// second one gets `Busy` straight away instead of queueing behind, or
+/// interleaving with, the first.
+struct LifecycleLock(Mutex<Option<String>>);
+
+/// Marks a lifecycle operation running until dropped.
+struct LifecycleOp<'a>(&'a LifecycleLock);
+
+impl LifecycleLock {
+    fn begin(&self, operation: &str) -> Result<LifecycleOp<'_>, EngineError> {
+        let mut current = self.0.lock_or_recover();
+        if let Some(running) = current.as_ref() {
+            return Err(EngineError::Busy {
+                operation: running.clone(),
+            });
+        }
+        *current = Some(operation.to_string());
+        Ok(LifecycleOp(self))
+    }
+
+    fn is_busy(&self) -> bool {
+        self.0.lock_or_recover().is_some()
+    }
+}
+
+impl Drop for LifecycleOp<'_> {
+    fn drop(&mut self) {
+        *self.0 .0.lock_or_recover() = None;
+    }
+}
+
 /// Persistent log file path for engine boot output.
 struct EngineLogPath(Mutex<PathBuf>);
 
-const ENGINE_PORT: u16 = 8765;
+/// Launcher configuration as stored in the app config dir, plus the env
+/// overrides read at startup. Only the stored values are ever saved.
+struct EngineConfigState(Mutex<EngineConfig>, config::EnvOverrides);
+
+impl EngineConfigState {
+    /// The stored config with env overrides applied, as a launch sees it.
+    fn current(&self) -> EngineConfig {
+        let mut config = self.0.lock_or_recover().clone();
+        self.1.apply(&mut config);
+        config
+    }
+}
+
+/// Config the managed engine was last launched with. It differs from the
+/// stored config by any per-launch overrides (safe mode, profiling).
+struct LaunchedConfig(Mutex<Option<EngineConfig>>);
+
+/// A healthy engine already serving on the port at startup (typically left
+/// by an earlier session) and adopted instead of being replaced: its pid.
+/// Managed externally, as it isn't our child, so it's signalled rather than
+/// waited on.
+struct AdoptedEngine(Mutex<Option<u32>>);
+
+/// The desktop shell's version, and the commit it was built from when the
+/// build had git to ask (see build.rs).
+const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
+const GIT_COMMIT: Option<&str> = option_env!("SOLAT_GIT_HASH");
+
+/// Holds the running engine's pid, in its data dir, for external tools and
+/// for adopting it after the launcher crashed. Later lines give its port
+/// (`port=<n>`, as it may not be the configured one) and, with
+/// `PIPED_OUTPUT_MARKER`, that its output goes through the launcher's pipes.
+const PID_FILE_NAME: &str = "engine.pid";
+const PID_FILE_PORT_PREFIX: &str = "port=";
+const PIPED_OUTPUT_MARKER: &str = "output=pipe";
+
+/// What the PID file says about the engine it was written for.
+struct PidRecord {
+    pid: u32,
+    /// Absent from files written before the port was recorded.
+    port: Option<u16>,
+    /// Its stdout and stderr are pipes read by the launcher that spawned it.
+    piped_output: bool,
+}
+
+fn pid_file_path(config: &EngineConfig) -> PathBuf {
+    resolve_data_dir(config).join(PID_FILE_NAME)
+}
+
+fn write_pid_file(config: &EngineConfig, pid: u32, port: u16, piped_output: bool) {
+    let path = pid_file_path(config);
+    let mut content = format!("{}\n{}{}\n", pid, PID_FILE_PORT_PREFIX, port);
+    if piped_output {
+        content.push_str(PIPED_OUTPUT_MARKER);
+        content.push('\n');
+    }
+    let written = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(&path, content));
+    if let Err(e) = written {
+        eprintln!(
+            "[SOLAT] WARNING: Failed to write PID file {}: {}",
+            path.display(),
+            e
+        );
+    }
+}
+
+fn read_pid_file(config: &EngineConfig) -> Option<PidRecord> {
+    let content = std::fs::read_to_string(pid_file_path(config)).ok()?;
+    let mut lines = content.lines().map(str::trim);
+    let pid = lines.next()?.parse().ok()?;
+    let lines: Vec<&str> = lines.collect();
+    Some(PidRecord {
+        pid,
+        port: lines
+            .iter()
+            .find_map(|line| line.strip_prefix(PID_FILE_PORT_PREFIX)?.parse().ok()),
+        piped_output: lines.contains(&PIPED_OUTPUT_MARKER),
+    })
+}
+
+fn remove_pid_file(config: &EngineConfig) {
+    let path = pid_file_path(config);
+    match std::fs::remove_file(&path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => eprintln!(
+            "[SOLAT] WARNING: Failed to remove PID file {}: {}",
+            path.display(),
+            e
+        ),
+        _ => {}
+    }
+}
+
+/// Config the engine was last launched with, else the stored one. Its data
+/// dir holds the PID file, and crash restarts reuse it so per-launch
+/// overrides (safe mode, profiling) survive them.
+fn running_config(app: &AppHandle) -> EngineConfig {
+    app.state::<LaunchedConfig>()
+        .0
+        .lock_or_recover()
+        .clone()
+        .unwrap_or_else(|| app.state::<EngineConfigState>().current())
+}
+
+/// When the current engine process was spawned, reset by every launch.
+struct EngineUptime(Mutex<Option<StartTime>>);
+
+struct StartTime {
+    at: Instant,
+    /// RFC 3339, UTC.
+    timestamp: String,
+}
+
+/// Note a freshly spawned engine as soon as it's stored: its start time, the
+/// config it was launched with and its PID file, so a launcher crash during
+/// the health wait still leaves the PID file pointing at it.
+fn record_engine_start(app: &AppHandle, config: &EngineConfig, pid: u32) {
+    let port = *app.state::<EnginePort>().0.lock_or_recover();
+    // `spawn_engine` pipes the output unless it goes to our terminal
+    write_pid_file(config, pid, port, config.log_target != LogTarget::Inherit);
+    *app.state::<LaunchedConfig>().0.lock_or_recover() = Some(config.clone());
+    *app.state::<EngineUptime>().0.lock_or_recover() = Some(StartTime {
+        at: Instant::now(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    });
+}
+
+/// How long the current engine has been up, if one was spawned.
+fn engine_uptime(app: &AppHandle) -> Option<Duration> {
+    let started = app.state::<EngineUptime>();
+    let started = started.0.lock_or_recover();
+    started.as_ref().map(|started| started.at.elapsed())
+}
+
+/// Port of the current (or most recent) engine launch; differs per launch
+/// with `port: "auto"`.
+struct EnginePort(Mutex<u16>);
+
+/// The port picked for a launch, for a frontend that can't assume 8765.
+const PORT_EVENT: &str = "engine://port";
 const HEALTH_WAIT_SECS: u64 = 12;
+/// Safe mode is a recovery path after a crash, so give the engine longer to boot.
+const SAFE_MODE_HEALTH_WAIT_SECS: u64 = 30;
+/// Past the health deadline, a boot whose log grew this recently (uv still
+/// installing dependencies, say) is waited for a while longer...
+const LOG_PROGRESS_GRACE: Duration = Duration::from_secs(15);
+/// ...but never past this, however much it logs.
+const HEALTH_WAIT_CEILING: Duration = Duration::from_secs(15 * 60);
+/// Consecutive health checks that get no HTTP answer at all from an open
+/// port before the boot gives up and blames a firewall.
+const FIREWALL_SUSPECT_ATTEMPTS: u32 = 3;
+/// Latest health payload seen while waiting for the engine to boot.
+const BOOT_HEALTH_EVENT: &str = "engine://boot-health";
+const BOOT_STATE_EVENT: &str = "engine://boot-state";
+/// Coarse boot progress for the splash: `{ phase, elapsed_ms }`.
+const BOOT_PHASE_EVENT: &str = "engine://boot-phase";
+/// A launch passed its health check: `{ port, boot_time_ms }`.
+const ENGINE_READY_EVENT: &str = "engine://ready";
+/// A launch exited, or wasn't healthy in time: `{ reason, log_tail }`.
+const BOOT_FAILED_EVENT: &str = "engine://boot-failed";
+/// No engine directory was found at startup: `{ checked: [path, ...] }`.
+const ENGINE_DIR_MISSING_EVENT: &str = "engine://dir-missing";
+/// How long a stopping engine gets to exit on SIGTERM before it's killed.
+const STOP_GRACE: Duration = Duration::from_secs(5);
 
 // ---------------------------------------------------------------------------
 // Port management
 // ---------------------------------------------------------------------------
 
-fn port_is_occupied() -> bool {
-    TcpStream::connect_timeout(
-        &format!("127.0.0.1:{}", ENGINE_PORT).parse().unwrap(),
-        Duration::from_millis(500),
-    )
-    .is_ok()
-}
-
-fn kill_port_occupant() {
-    println!("[SOLAT] Killing stale process on port {}...", ENGINE_PORT);
-    let output = StdCommand::new("lsof")
-        .args(["-ti", &format!(":{}", ENGINE_PORT)])
-        .output();
-
-    if let Ok(output) = output {
-        let pids = String::from_utf8_lossy(&output.stdout);
-        for pid_str in pids.split_whitespace() {
-            if let Ok(pid) = pid_str.trim().parse::<i32>() {
-                println!("[SOLAT] Killing PID {} on port {}", pid, ENGINE_PORT);
-                let _ = StdCommand::new("kill")
-                    .args(["-9", &pid.to_string()])
-                    .output();
-            }
-        }
-    }
-    std::thread::sleep(Duration::from_millis(500));
+fn port_is_occupied(port: u16) -> bool {
+    port_accepts(SocketAddr::from((Ipv4Addr::LOCALHOST, port)))
+}
+
+fn port_accepts(addr: SocketAddr) -> bool {
+    TcpStream::connect_timeout(&addr, Duration::from_millis(500)).is_ok()
+}
+
+/// Where the launcher reaches the engine: the configured host, else the
+/// address it binds (loopback for a wildcard bind), on the current engine
+/// port.
+fn engine_addr(app: &AppHandle, config: &EngineConfig) -> Result<SocketAddr, String> {
+    let ip = match config.engine_host.as_deref() {
+        Some(host) => http::resolve_engine_host(Some(host))?,
+        None => match config::effective_bind_host(config) {
+            IpAddr::V4(ip) if ip.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+            IpAddr::V6(ip) if ip.is_unspecified() => IpAddr::V6(Ipv6Addr::LOCALHOST),
+            ip => ip,
+        },
+    };
+    let port = *app.state::<EnginePort>().0.lock_or_recover();
+    Ok(SocketAddr::new(ip, port))
 }
 
-fn ensure_port_free() {
-    if port_is_occupied() {
-        kill_port_occupant();
-        if port_is_occupied() {
+/// Let the OS pick a free port: bind `:0`, read it back, release it. Another
+/// process could grab it before uvicorn binds, but the window is tiny.
+fn ephemeral_port() -> Result<u16, String> {
+    TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .map_err(|e| format!("Failed to find a free port: {}", e))
+}
+
+/// Whether whatever listens on `port` answers `/health` the way a SOLAT
+/// engine does: a JSON object with `status` and `uptime_seconds`, in any
+/// state of boot. Anything else, including a listener that resets the
+/// connection or doesn't speak HTTP, is someone else's.
+fn is_our_engine(client: &reqwest::Client, port: u16) -> bool {
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    http::block_on(http::check_health(client, addr))
+        .ok()
+        .and_then(|response| response.json)
+        .is_some_and(|json| {
+            json.get("status").is_some_and(|status| status.is_string())
+                && json.get("uptime_seconds").is_some()
+        })
+}
+
+/// `preferred` if it's free or held by a stale SOLAT engine (which
+/// `ensure_port_free` then kills), else an ephemeral port: an unrelated
+/// service on our port is left alone.
+fn find_free_port(client: &reqwest::Client, preferred: u16) -> u16 {
+    if !port_is_occupied(preferred) || is_our_engine(client, preferred) {
+        return preferred;
+    }
+    match ephemeral_port() {
+        Ok(port) => {
             eprintln!(
-                "[SOLAT] WARNING: Port {} still occupied after kill attempt",
-                ENGINE_PORT
+                "[SOLAT] WARNING: Port {} is held by another application, using {} instead",
+                preferred, port
             );
+            port
+        }
+        Err(e) => {
+            eprintln!("[SOLAT] WARNING: {}; trying port {} anyway", e, preferred);
+            preferred
+        }
+    }
+}
+
+/// Decide the port for a launch, record it in `EnginePort` and tell the
+/// frontend.
+fn select_port(app: &AppHandle, config: &EngineConfig) -> Result<u16, String> {
+    let port = match config.port {
+        PortSetting::Fixed(port) => find_free_port(&app.state::<http::HttpClient>().0, port),
+        PortSetting::Auto => {
+            let port = ephemeral_port()?;
+            println!("[SOLAT] Auto port: using {}", port);
+            port
+        }
+    };
+    *app.state::<EnginePort>().0.lock_or_recover() = port;
+    let _ = app.emit(PORT_EVENT, port);
+    Ok(port)
+}
+
+/// `pid=1234` entries out of `ss -lptn` rows for `port`, e.g.
+/// `LISTEN 0 2048 127.0.0.1:8765 0.0.0.0:* users:(("python3",pid=1234,fd=6))`.
+#[cfg(unix)]
+fn parse_ss_pids(output: &str, port: u16) -> Vec<u32> {
+    let suffix = format!(":{}", port);
+    output
+        .lines()
+        .filter(|line| {
+            line.split_whitespace()
+                .nth(3)
+                .is_some_and(|local| local.ends_with(&suffix))
+        })
+        .flat_map(|line| line.split("pid=").skip(1))
+        .filter_map(|rest| {
+            let digits = rest.split(|c: char| !c.is_ascii_digit()).next()?;
+            digits.parse().ok()
+        })
+        .collect()
+}
+
+/// PIDs out of `lsof -t` or `fuser` stdout: whitespace-separated, each
+/// possibly followed by one of fuser's access letters (e.g. `1234e`).
+#[cfg(unix)]
+fn parse_pid_list(output: &str) -> Vec<u32> {
+    output
+        .split_whitespace()
+        .filter_map(|pid| {
+            pid.trim_end_matches(|c: char| c.is_ascii_alphabetic())
+                .parse()
+                .ok()
+        })
+        .collect()
+}
+
+/// PIDs listening on `port`. Minimal distros often lack `lsof`, so `ss` and
+/// then `fuser` are tried after it; a tool that finds nothing hands over to
+/// the next too, as each sees a little differently. Fails only when none of
+/// them is installed, since then an occupied port can't be cleared.
+#[cfg(unix)]
+fn pids_on_port(port: u16) -> Result<Vec<u32>, String> {
+    let lsof_args = [
+        "-ti".to_string(),
+        format!("tcp:{}", port),
+        "-sTCP:LISTEN".to_string(),
+    ];
+    let ss_args = ["-lptn".to_string()];
+    let fuser_args = [format!("{}/tcp", port)];
+    let tools: [(&str, &[String]); 3] = [
+        ("lsof", &lsof_args),
+        ("ss", &ss_args),
+        ("fuser", &fuser_args),
+    ];
+
+    let mut ran_any = false;
+    for (tool, args) in tools {
+        // All of them exit non-zero when nothing matches, so go by the
+        // output alone. fuser writes its `8765/tcp:` label to stderr and
+        // only the pids to stdout.
+        let output = match StdCommand::new(tool).args(args).output() {
+            Ok(output) => output,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => {
+                eprintln!("[SOLAT] WARNING: Failed to run {}: {}", tool, e);
+                continue;
+            }
+        };
+        ran_any = true;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut pids = if tool == "ss" {
+            parse_ss_pids(&stdout, port)
         } else {
-            println!("[SOLAT] Port {} freed successfully", ENGINE_PORT);
+            parse_pid_list(&stdout)
+        };
+        pids.sort_unstable();
+        pids.dedup();
+        if !pids.is_empty() {
+            return Ok(pids);
+        }
+    }
+    if ran_any {
+        Ok(Vec::new())
+    } else {
+        Err(format!(
+            "Can't find what holds port {}: none of lsof, ss or fuser is installed; install one of them (e.g. lsof)",
+            port
+        ))
+    }
+}
+
+/// PIDs listening on `port`, from `netstat -ano`'s
+/// `TCP  127.0.0.1:8765  0.0.0.0:0  LISTENING  1234` rows.
+#[cfg(windows)]
+fn pids_on_port(port: u16) -> Result<Vec<u32>, String> {
+    let output = StdCommand::new("netstat")
+        .args(["-ano", "-p", "TCP"])
+        .output()
+        .map_err(|e| format!("Failed to run netstat: {}", e))?;
+    let suffix = format!(":{}", port);
+    let mut pids: Vec<u32> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                [_, local, _, "LISTENING", pid] if local.ends_with(&suffix) => pid.parse().ok(),
+                _ => None,
+            }
+        })
+        .collect();
+    pids.sort_unstable();
+    pids.dedup();
+    Ok(pids)
+}
+
+#[cfg(unix)]
+fn force_kill_pid(pid: u32) -> Result<(), String> {
+    let output = StdCommand::new("kill")
+        .args(["-9", &pid.to_string()])
+        .output()
+        .map_err(|e| format!("Failed to run kill: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+#[cfg(windows)]
+fn force_kill_pid(pid: u32) -> Result<(), String> {
+    let output = StdCommand::new("taskkill")
+        .args(["/F", "/PID", &pid.to_string()])
+        .output()
+        .map_err(|e| format!("Failed to run taskkill: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// Force-kill whatever listens on `port`, failing if any of it survives.
+fn kill_pid_on_port(port: u16) -> Result<(), String> {
+    println!("[SOLAT] Killing stale process on port {}...", port);
+    let pids = pids_on_port(port)?;
+    if pids.is_empty() {
+        return Err(format!("No process found listening on port {}", port));
+    }
+    let mut failures = Vec::new();
+    for pid in pids {
+        println!("[SOLAT] Killing PID {} on port {}", pid, port);
+        match force_kill_pid(pid) {
+            Ok(()) => println!("[SOLAT] Killed PID {}", pid),
+            Err(e) => {
+                eprintln!("[SOLAT] WARNING: Failed to kill PID {}: {}", pid, e);
+                failures.push(format!("PID {}: {}", pid, e));
+            }
         }
     }
+    std::thread::sleep(Duration::from_millis(500));
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Could not kill the process on port {} ({})",
+            port,
+            failures.join("; ")
+        ))
+    }
+}
+
+/// Kill a stale SOLAT engine holding `port`. Anything else there is refused
+/// rather than killed.
+fn ensure_port_free(client: &reqwest::Client, port: u16) -> Result<(), EngineError> {
+    if !port_is_occupied(port) {
+        return Ok(());
+    }
+    if !is_our_engine(client, port) {
+        return Err(EngineError::PortOccupiedForeign { port });
+    }
+    kill_pid_on_port(port)?;
+    if port_is_occupied(port) {
+        eprintln!(
+            "[SOLAT] WARNING: Port {} still occupied after kill attempt",
+            port
+        );
+        return Err(format!("Port {} is still in use after killing its occupant", port).into());
+    }
+    println!("[SOLAT] Port {} freed successfully", port);
+    Ok(())
 }
 
 // ---------------------------------------------------------------------------
 // Engine directory + uv resolution
 // ---------------------------------------------------------------------------
 
-fn find_engine_dir() -> Option<PathBuf> {
+/// `engine/` under the app's Tauri resource dir, where a release bundle
+/// ships it. Only known once `setup` has an `AppHandle`.
+static RESOURCE_ENGINE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+fn register_resource_engine_dir(app: &AppHandle) {
+    match app.path().resource_dir() {
+        Ok(dir) => {
+            let _ = RESOURCE_ENGINE_DIR.set(dir.join("engine"));
+        }
+        Err(e) => eprintln!(
+            "[SOLAT] WARNING: No resource dir to look for a bundled engine in: {}",
+            e
+        ),
+    }
+}
+
+/// Engine directory chosen with `set_engine_dir`. Process-wide (like
+/// `UV_PATH`) so every resolution sees it, not just spawns.
+struct EngineDir(Mutex<Option<PathBuf>>);
+
+static ENGINE_DIR: EngineDir = EngineDir(Mutex::new(None));
+
+/// `SOLAT_ENGINE_DIR`, if set.
+fn env_engine_dir() -> Option<PathBuf> {
+    std::env::var_os("SOLAT_ENGINE_DIR")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+}
+
+fn is_engine_dir(dir: &Path) -> bool {
+    dir.join("solat_engine").is_dir()
+}
+
+/// Directories `find_engine_dir` tries, in order: one chosen at runtime or
+/// via `SOLAT_ENGINE_DIR`, a bundled engine, then the dev project layout.
+fn engine_dir_candidates() -> Vec<PathBuf> {
     let candidates = [
+        ENGINE_DIR.0.lock_or_recover().clone(),
+        env_engine_dir(),
+        RESOURCE_ENGINE_DIR.get().cloned(),
         // From project root
         std::env::current_dir().ok().map(|p| p.join("engine")),
         // From src-tauri/
@@ -79,44 +580,183 @@ fn find_engine_dir() -> Option<PathBuf> {
         // Absolute fallback via CARGO_MANIFEST_DIR
         Some(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../../engine")),
     ];
+    candidates.into_iter().flatten().collect()
+}
 
-    for candidate in candidates.into_iter().flatten() {
-        if let Ok(resolved) = candidate.canonicalize() {
-            if resolved.join("solat_engine").is_dir() {
-                return Some(resolved);
-            }
+fn find_engine_dir() -> Option<PathBuf> {
+    engine_dir_candidates().into_iter().find_map(|candidate| {
+        candidate
+            .canonicalize()
+            .ok()
+            .filter(|resolved| is_engine_dir(resolved))
+    })
+}
+
+/// Files a launch needs in the engine directory, relative to it.
+const ENGINE_DIR_REQUIRED_FILES: [&str; 3] = [
+    "pyproject.toml",
+    "solat_engine/__init__.py",
+    "solat_engine/main.py",
+];
+
+/// Check `dir` holds what uvicorn will import, so a partial checkout fails
+/// with the missing files named instead of a Python traceback.
+fn validate_engine_dir(dir: &Path) -> Result<(), String> {
+    let missing: Vec<&str> = ENGINE_DIR_REQUIRED_FILES
+        .into_iter()
+        .filter(|file| !dir.join(file).is_file())
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    Err(format!(
+        "Engine install incomplete: {} is missing {}. Re-clone or restore the engine directory.",
+        dir.display(),
+        missing.join(", ")
+    ))
+}
+
+/// `find_engine_dir`, or an error listing every place that was tried.
+fn require_engine_dir() -> Result<PathBuf, EngineError> {
+    find_engine_dir().ok_or_else(|| EngineError::EngineDirMissing {
+        checked: engine_dir_candidates()
+            .iter()
+            .map(|candidate| candidate.display().to_string())
+            .collect(),
+    })
+}
+
+/// Use `path` as the engine directory for every later launch, in place of
+/// `SOLAT_ENGINE_DIR` and the usual candidates. Returns it canonicalized.
+#[tauri::command]
+async fn set_engine_dir(path: String) -> Result<String, String> {
+    let dir = PathBuf::from(path.trim())
+        .canonicalize()
+        .map_err(|e| format!("Engine directory {} is not accessible: {}", path, e))?;
+    if !is_engine_dir(&dir) {
+        return Err(format!(
+            "{} has no solat_engine/ folder; choose the root of the engine checkout",
+            dir.display()
+        ));
+    }
+    validate_engine_dir(&dir)?;
+    println!("[SOLAT] Engine directory set to {}", dir.display());
+    *ENGINE_DIR.0.lock_or_recover() = Some(dir.clone());
+    Ok(dir.display().to_string())
+}
+
+/// One place binary resolution looked, for `get_resolution_diagnostics`.
+#[derive(Debug, Clone, serde::Serialize)]
+struct CheckedLocation {
+    /// How the location was found, e.g. `login shell` or `fallback`.
+    via: String,
+    path: String,
+    exists: bool,
+}
+
+impl CheckedLocation {
+    fn new(via: &str, path: &Path) -> Self {
+        Self {
+            via: via.to_string(),
+            path: path.display().to_string(),
+            exists: path.exists(),
         }
     }
-    None
 }
 
-/// Resolve the absolute path to `uv` using a login shell (picks up ~/.zshrc PATH).
-/// Falls back to common known locations if shell resolution fails.
+/// Login shells to ask for `PATH`: the user's `$SHELL`, then zsh (the macOS
+/// default) if that's a different one. bash, zsh and fish all take `-lc`.
+fn login_shells() -> Vec<PathBuf> {
+    let mut shells: Vec<PathBuf> = std::env::var_os("SHELL")
+        .filter(|shell| !shell.is_empty())
+        .map(PathBuf::from)
+        .into_iter()
+        .collect();
+    let zsh = PathBuf::from("/bin/zsh");
+    if !shells.contains(&zsh) {
+        shells.push(zsh);
+    }
+    shells.retain(|shell| shell.exists());
+    shells
+}
+
+fn shell_name(shell: &Path) -> String {
+    shell
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| shell.display().to_string())
+}
+
+/// `PATH` as the first working login shell sets it up, with that shell.
+fn login_shell_path() -> Option<(String, String)> {
+    login_shells().into_iter().find_map(|shell| {
+        // fish keeps PATH as a list, which "$PATH" would join with spaces
+        let script = if shell_name(&shell) == "fish" {
+            "string join : $PATH"
+        } else {
+            "printf %s \"$PATH\""
+        };
+        StdCommand::new(&shell)
+            .args(["-lc", script])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| {
+                (
+                    shell.display().to_string(),
+                    String::from_utf8_lossy(&output.stdout).trim().to_string(),
+                )
+            })
+    })
+}
+
+/// uv as last resolved; login shells are slow to start, so restarts reuse
+/// it. Only a hit is cached, so a missing uv is looked for again next time.
+static UV_PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Resolve the absolute path to `uv` using a login shell (picks up the PATH
+/// from the user's shell rc files). Falls back to common known locations if
+/// shell resolution fails. A cached path is reused while it still exists.
 fn resolve_uv_path() -> Option<PathBuf> {
-    // Try login shell first (works even when Tauri is launched from Finder)
-    if let Ok(output) = StdCommand::new("/bin/zsh")
-        .args(["-lc", "command -v uv"])
-        .output()
-    {
+    let mut cached = UV_PATH.lock_or_recover();
+    if let Some(uv) = cached.as_ref().filter(|uv| uv.exists()) {
+        return Some(uv.clone());
+    }
+    *cached = find_uv(&mut Vec::new());
+    cached.clone()
+}
+
+/// Uncached `resolve_uv_path`, recording every location it looked at in
+/// `checked`.
+fn find_uv(checked: &mut Vec<CheckedLocation>) -> Option<PathBuf> {
+    // Try login shells first (works even when Tauri is launched from Finder)
+    for shell in login_shells() {
+        let name = shell_name(&shell);
+        let Ok(output) = StdCommand::new(&shell)
+            .args(["-lc", "command -v uv"])
+            .output()
+        else {
+            continue;
+        };
         let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
         if !path.is_empty() {
             let p = PathBuf::from(&path);
+            checked.push(CheckedLocation::new(&format!("login shell ({})", name), &p));
             if p.exists() {
-                println!("[SOLAT] Resolved uv via login shell: {}", path);
+                println!("[SOLAT] Resolved uv via {} login shell: {}", name, path);
                 return Some(p);
             }
         }
     }
 
-    // Fallback: check common install locations
-    let fallbacks = [
-        dirs::home_dir().map(|h| h.join(".local/bin/uv")),
-        dirs::home_dir().map(|h| h.join(".cargo/bin/uv")),
-        Some(PathBuf::from("/usr/local/bin/uv")),
-        Some(PathBuf::from("/opt/homebrew/bin/uv")),
-    ];
+    #[cfg(windows)]
+    if let Some(uv) = where_uv(checked) {
+        return Some(uv);
+    }
 
-    for candidate in fallbacks.into_iter().flatten() {
+    // Fallback: check common install locations
+    for candidate in uv_fallbacks().into_iter().flatten() {
+        checked.push(CheckedLocation::new("fallback", &candidate));
         if candidate.exists() {
             println!("[SOLAT] Found uv at fallback: {}", candidate.display());
             return Some(candidate);
@@ -126,304 +766,2221 @@ fn resolve_uv_path() -> Option<PathBuf> {
     None
 }
 
-// ---------------------------------------------------------------------------
-// Log file management
-// ---------------------------------------------------------------------------
+/// Windows has no login shell to ask; `where` searches the PATH (with
+/// `PATHEXT`, so it finds `uv.exe`) the way cmd would.
+#[cfg(windows)]
+fn where_uv(checked: &mut Vec<CheckedLocation>) -> Option<PathBuf> {
+    let output = StdCommand::new("cmd")
+        .args(["/C", "where", "uv"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let path = PathBuf::from(stdout.lines().next()?.trim());
+    checked.push(CheckedLocation::new("where", &path));
+    if path.exists() {
+        println!("[SOLAT] Resolved uv via where: {}", path.display());
+        Some(path)
+    } else {
+        None
+    }
+}
+
+#[cfg(unix)]
+fn uv_fallbacks() -> [Option<PathBuf>; 4] {
+    [
+        dirs::home_dir().map(|h| h.join(".local/bin/uv")),
+        dirs::home_dir().map(|h| h.join(".cargo/bin/uv")),
+        Some(PathBuf::from("/usr/local/bin/uv")),
+        Some(PathBuf::from("/opt/homebrew/bin/uv")),
+    ]
+}
 
-fn engine_log_path(engine_dir: &PathBuf) -> PathBuf {
-    let log_dir = engine_dir.join("data").join("logs");
-    let _ = fs::create_dir_all(&log_dir);
-    log_dir.join("engine-boot.log")
+/// The uv installer's default, the older per-user programs dir, and cargo's.
+#[cfg(windows)]
+fn uv_fallbacks() -> [Option<PathBuf>; 3] {
+    [
+        dirs::home_dir().map(|h| h.join(r".local\bin\uv.exe")),
+        dirs::data_local_dir().map(|d| d.join(r"Programs\uv\uv.exe")),
+        std::env::var_os("CARGO_HOME")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|h| h.join(".cargo")))
+            .map(|cargo| cargo.join(r"bin\uv.exe")),
+    ]
 }
 
-fn read_log_tail(path: &PathBuf, lines: usize) -> String {
-    match fs::read_to_string(path) {
-        Ok(content) => {
-            let all_lines: Vec<&str> = content.lines().collect();
-            let start = if all_lines.len() > lines {
-                all_lines.len() - lines
-            } else {
-                0
-            };
-            all_lines[start..].join("\n")
+#[derive(Debug, Clone, serde::Serialize)]
+struct ResolutionDiagnostics {
+    /// `PATH` as this process inherited it (minimal for Finder/Explorer launches).
+    path_env: Option<String>,
+    /// Login shell uv lookup asks first (`$SHELL`, else zsh).
+    login_shell: Option<String>,
+    /// `PATH` as that login shell sets it up, which is what uv lookup uses.
+    login_shell_path: Option<String>,
+    /// Every location checked, in order, uv first and then python.
+    checked_locations: Vec<CheckedLocation>,
+    found_uv: Option<String>,
+    /// Interpreter a launch without uv would fall back to.
+    found_python: Option<String>,
+    /// What a launch would run, and why.
+    explanation: String,
+}
+
+/// The python the no-uv fallback in `spawn_engine` would run: the engine
+/// venv's, else `python3` from `PATH`.
+fn find_python(engine_dir: Option<&Path>, checked: &mut Vec<CheckedLocation>) -> Option<PathBuf> {
+    if let Some(engine_dir) = engine_dir {
+        let venv_python = venv::venv_python(engine_dir);
+        checked.push(CheckedLocation::new("engine venv", &venv_python));
+        if venv_python.exists() {
+            return Some(venv_python);
         }
-        Err(_) => String::from("(no log file found)"),
     }
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join("python3"))
+        .find(|candidate| {
+            checked.push(CheckedLocation::new("PATH", candidate));
+            candidate.exists()
+        })
+}
+
+/// Where the engine keeps data (and we keep boot logs): the configured
+/// override, or `engine/data`.
+fn resolve_data_dir(config: &EngineConfig) -> PathBuf {
+    config.data_dir.clone().unwrap_or_else(|| {
+        find_engine_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("data")
+    })
 }
 
 // ---------------------------------------------------------------------------
 // Engine spawn
 // ---------------------------------------------------------------------------
 
-fn spawn_engine(log_path: &PathBuf) -> Result<Child, String> {
-    let engine_dir = find_engine_dir().ok_or("Could not find engine directory")?;
+/// Path the profile of the next spawn goes to when profiling is enabled,
+/// recorded in managed state so stopping the engine can report it.
+fn prepare_profile_output(app: &AppHandle, config: &EngineConfig) -> Option<PathBuf> {
+    let output = config
+        .profile
+        .then(|| profiling::output_path(&resolve_data_dir(config)));
+    *app.state::<ProfileOutput>().0.lock_or_recover() = output.clone();
+    output
+}
+
+fn spawn_engine(
+    log_path: &Path,
+    config: &EngineConfig,
+    port: u16,
+    profile_output: Option<&Path>,
+    model: Option<&str>,
+    memory_log: &MemoryLog,
+) -> Result<Child, EngineError> {
+    let engine_dir = require_engine_dir()?;
+    validate_engine_dir(&engine_dir)?;
+    config::validate_extra_uvicorn_args(&config.extra_uvicorn_args).map_err(|e| e.to_string())?;
 
     println!("[SOLAT] Starting engine from: {}", engine_dir.display());
     println!("[SOLAT] Log file: {}", log_path.display());
 
-    // Open log file for stdout+stderr redirect (not piped — avoids buffer deadlock)
-    let log_file = fs::File::create(log_path)
-        .map_err(|e| format!("Failed to create log file: {}", e))?;
-    let log_file_err = log_file
-        .try_clone()
-        .map_err(|e| format!("Failed to clone log file handle: {}", e))?;
+    // Output goes to the log through the pumps, which also keep it in
+    // memory, so an unwritable log leaves the engine startable and visible
+    let opened = if config.daily_log_rotation {
+        logs::open_daily_log(log_path)
+    } else {
+        if !logs::rotate_log_if_needed(log_path, logs::BOOT_LOG_ROTATE_BYTES) {
+            logs::keep_previous_log(log_path);
+        }
+        logs::create_log(log_path).map_err(|e| format!("Failed to create log file: {}", e))
+    };
+    let mut log_file = match opened {
+        Ok(file) => Some(file),
+        Err(e) => {
+            eprintln!(
+                "[SOLAT] WARNING: {}; engine output is only kept in memory",
+                e
+            );
+            None
+        }
+    };
 
     // Resolve uv path (GUI apps don't inherit terminal PATH)
     let uv_path = resolve_uv_path();
 
-    let child = if let Some(uv) = &uv_path {
+    let host = config::announce_bind_host(config).to_string();
+    let uvicorn_args = [
+        "-m",
+        "uvicorn",
+        "solat_engine.main:app",
+        "--host",
+        &host,
+        "--port",
+        &port.to_string(),
+        "--log-level",
+        config::effective_log_level(config),
+    ];
+
+    // Set when the venv is missing and auto setup will create it; that runs
+    // after the log header, like the pre-start hook
+    let mut bootstrap_python = None;
+    let (mut command, launcher) = if let Some(uv) = &uv_path {
         println!("[SOLAT] Using uv at: {}", uv.display());
-        StdCommand::new(uv)
-            .args([
-                "run",
-                "python",
-                "-m",
-                "uvicorn",
-                "solat_engine.main:app",
-                "--host",
-                "127.0.0.1",
-                "--port",
-                &ENGINE_PORT.to_string(),
-                "--log-level",
-                "info",
-            ])
-            .current_dir(&engine_dir)
-            .stdout(Stdio::from(log_file))
-            .stderr(Stdio::from(log_file_err))
-            .spawn()
-            .map_err(|e| format!("Failed to spawn engine via uv: {}", e))?
+        let mut command = StdCommand::new(uv);
+        command
+            .args(["run", "python"])
+            .args(uvicorn_args)
+            .args(&config.extra_uvicorn_args);
+        (command, "uv")
     } else {
         // Fallback: try python3 directly (assumes venv is activated or system python works)
         eprintln!("[SOLAT] uv not found, falling back to python3 -m uvicorn");
-        let venv_python = engine_dir.join(".venv/bin/python3");
-        let python_cmd = if venv_python.exists() {
+        let venv_python = venv::venv_python(&engine_dir);
+        if !venv_python.exists() && venv::auto_setup_enabled() {
+            if log_file.is_none() {
+                return Err("Auto setup needs a writable boot log for its progress"
+                    .to_string()
+                    .into());
+            }
+            bootstrap_python = Some(
+                find_python(None, &mut Vec::new()).unwrap_or_else(|| PathBuf::from("python3")),
+            );
+        }
+        let python_cmd = if venv_python.exists() || bootstrap_python.is_some() {
             venv_python.to_string_lossy().to_string()
         } else {
             "python3".to_string()
         };
 
         println!("[SOLAT] Using python at: {}", python_cmd);
-        StdCommand::new(&python_cmd)
-            .args([
-                "-m",
-                "uvicorn",
-                "solat_engine.main:app",
-                "--host",
-                "127.0.0.1",
-                "--port",
-                &ENGINE_PORT.to_string(),
-                "--log-level",
-                "info",
-            ])
-            .current_dir(&engine_dir)
-            .stdout(Stdio::from(log_file))
-            .stderr(Stdio::from(log_file_err))
-            .spawn()
-            .map_err(|e| format!("Failed to spawn engine via python3: {}", e))?
+        let mut command = StdCommand::new(&python_cmd);
+        command.args(uvicorn_args).args(&config.extra_uvicorn_args);
+        (command, "python3")
     };
 
-    Ok(child)
-}
-
-/// Kill stale port occupant, spawn engine, wait for health.
-fn force_start_engine(log_path: &PathBuf) -> Result<Child, String> {
-    ensure_port_free();
-    let mut child = spawn_engine(log_path)?;
-    let pid = child.id();
-    println!("[SOLAT] Engine spawned (pid {}), waiting for health...", pid);
-
-    // Wait for engine to become healthy (or detect early exit)
-    let start = Instant::now();
-    let deadline = Duration::from_secs(HEALTH_WAIT_SECS);
-
-    while start.elapsed() < deadline {
-        // Check if child exited early
-        match child.try_wait() {
-            Ok(Some(status)) => {
-                let tail = read_log_tail(log_path, 20);
-                return Err(format!(
-                    "Engine exited immediately with status: {}.\nLast log lines:\n{}",
-                    status, tail
-                ));
+    if let Some(output) = profile_output {
+        match profiling::find_py_spy(&engine_dir) {
+            Some(py_spy) => {
+                println!("[SOLAT] Profiling with py-spy at: {}", py_spy.display());
+                command = profiling::wrap_command(&py_spy, output, &command);
             }
-            Ok(None) => {} // still running, good
-            Err(e) => {
-                return Err(format!("Failed to check engine status: {}", e));
+            None => {
+                eprintln!("[SOLAT] WARNING: py-spy not found, asking the engine to profile itself");
+                command
+                    .env("SOLAT_PROFILE", "1")
+                    .env("SOLAT_PROFILE_OUTPUT", output);
             }
         }
-
-        // Check if health endpoint responds
-        if port_is_occupied() {
-            // Port is open — try an actual HTTP health check
-            if let Ok(output) = StdCommand::new("curl")
-                .args([
-                    "-sS",
-                    "--max-time",
-                    "2",
-                    &format!("http://127.0.0.1:{}/health", ENGINE_PORT),
-                ])
-                .output()
-            {
-                let body = String::from_utf8_lossy(&output.stdout);
-                if body.contains("healthy") {
-                    println!(
-                        "[SOLAT] Engine healthy after {:.1}s",
-                        start.elapsed().as_secs_f64()
-                    );
-                    return Ok(child);
-                }
-            }
+    }
+    let launcher = match config.launch_wrapper.split_first() {
+        Some((program, wrapper_args)) => {
+            println!("[SOLAT] Launching through wrapper: {}", program);
+            command = process::prefix_command(program, wrapper_args, &command);
+            program.as_str()
         }
+        None => launcher,
+    };
 
-        std::thread::sleep(Duration::from_millis(500));
+    // First, so the settings the launcher manages below win over the file
+    let env_file = engine_dir.join(".env");
+    let file_env = config::load_env_file(&env_file);
+    if !file_env.is_empty() {
+        println!(
+            "[SOLAT] Passing {} variable(s) from {}",
+            file_env.len(),
+            env_file.display()
+        );
+        command.envs(file_env);
+    }
+    if config.safe_mode {
+        println!("[SOLAT] Safe mode enabled: optional engine features disabled");
+        command.env("SOLAT_SAFE_MODE", "1");
+    }
+    if let Some(data_dir) = &config.data_dir {
+        println!("[SOLAT] Using data dir: {}", data_dir.display());
+        command.env("SOLAT_DATA_DIR", data_dir);
+    }
+    if let Some(model) = model {
+        println!("[SOLAT] Using model: {}", model);
+        command.env(model::MODEL_ENV, model);
+    }
+    if let Some(device) = &config.device {
+        println!("[SOLAT] Using device: {}", device);
+        command.envs(config::device_env(device)?);
     }
 
-    // Timeout — engine is still running but not healthy
-    let tail = read_log_tail(log_path, 20);
-    eprintln!(
-        "[SOLAT] WARNING: Engine pid {} not healthy after {}s. Log tail:\n{}",
-        pid, HEALTH_WAIT_SECS, tail
-    );
-    // Return the child anyway — splash screen will keep polling
-    Ok(child)
-}
-
-// ---------------------------------------------------------------------------
-// Tauri commands
-// ---------------------------------------------------------------------------
+    process::apply_priority(&mut command, config.process_priority);
+    process::apply_memory_limit(&mut command, config.memory_limit_mb);
+    command.current_dir(&engine_dir);
 
-#[tauri::command]
-async fn start_engine(
-    proc_state: tauri::State<'_, EngineProcess>,
-    log_state: tauri::State<'_, EngineLogPath>,
-) -> Result<String, String> {
-    // Kill existing managed child
-    {
-        let mut guard = proc_state.0.lock().map_err(|e| e.to_string())?;
-        if let Some(ref mut child) = *guard {
-            let _ = child.kill();
-            let _ = child.wait();
-            *guard = None;
+    // First line of the log: exactly what we ran, so a failed start can be
+    // reproduced by hand. The pre-start hook's and any venv setup's output
+    // follow it, then the child's, in the same file.
+    let description = process::describe_command(&command);
+    let header = format!("[SOLAT] Launch command: {}", description);
+    println!("{}", header);
+    if let Some(file) = log_file.as_mut() {
+        if let Err(e) = writeln!(file, "{}", header) {
+            eprintln!("[SOLAT] WARNING: Failed to write log header: {}", e);
         }
     }
+    memory_log.push(header);
 
-    let log_path = log_state.0.lock().map_err(|e| e.to_string())?.clone();
-    let child = force_start_engine(&log_path)?;
-    let pid = child.id();
-
-    {
-        let mut guard = proc_state.0.lock().map_err(|e| e.to_string())?;
-        *guard = Some(child);
+    hooks::run(
+        "pre-start hook",
+        &config.pre_start_hook,
+        &engine_dir,
+        log_file.as_mut(),
+    )
+    .map_err(|e| format!("Engine start aborted: {}", e))?;
+    if let (Some(python), Some(log)) = (&bootstrap_python, log_file.as_mut()) {
+        venv::bootstrap(&engine_dir, python, log)
+            .map_err(|e| format!("Auto setup failed: {}; see the boot log", e))?;
     }
 
-    Ok(format!("Engine started (pid {})", pid))
-}
-
-#[tauri::command]
-async fn stop_engine(state: tauri::State<'_, EngineProcess>) -> Result<String, String> {
-    let mut guard = state.0.lock().map_err(|e| e.to_string())?;
+    let (stdout, stderr) = if config.log_target == LogTarget::Inherit {
+        println!("[SOLAT] Engine output goes to this terminal, not the log file");
+        (Stdio::inherit(), Stdio::inherit())
+    } else {
+        (Stdio::piped(), Stdio::piped())
+    };
+    let mut child = command.stdout(stdout).stderr(stderr).spawn().map_err(|e| {
+        // Only the bare python3 fallback can point at a missing uv
+        if e.kind() == std::io::ErrorKind::NotFound && launcher == "python3" {
+            EngineError::UvNotFound {
+                message: format!(
+                    "uv is not installed and no python3 was found to fall back to ({}); \
+                         install uv from https://docs.astral.sh/uv/",
+                    e
+                ),
+            }
+        } else {
+            EngineError::SpawnFailed {
+                launcher: launcher.to_string(),
+                message: e.to_string(),
+            }
+        }
+    })?;
+    let (echo_out, echo_err) = if config.log_target == LogTarget::Both {
+        (Some(Echo::Stdout), Some(Echo::Stderr))
+    } else {
+        (None, None)
+    };
+    let err_log = log_file.as_ref().and_then(|file| file.try_clone().ok());
+    if let Some(out) = child.stdout.take() {
+        memory_log::spawn_pump(out, log_file, echo_out, memory_log.clone());
+    }
+    if let Some(err) = child.stderr.take() {
+        memory_log::spawn_pump(err, err_log, echo_err, memory_log.clone());
+    }
+    process::verify_priority(&child, config.process_priority);
+
+    Ok(child)
+}
+
+#[derive(serde::Serialize)]
+struct BootHealthEvent {
+    elapsed_ms: u64,
+    /// The health body as JSON (e.g. `{"status":"starting","stage":"loading_model"}`),
+    /// or as a plain string if it isn't JSON.
+    body: serde_json::Value,
+}
+
+/// What the boot-time health checks currently see, announced on each change
+/// so the splash can tell "waiting for server" from "server errored".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum BootState {
+    /// Connection refused: uvicorn hasn't bound the port yet.
+    WaitingForServer,
+    /// Answering, but `/health` isn't healthy yet.
+    Initializing,
+    /// Answering `/health` with a 5xx: up, but erroring.
+    ServerError,
+    Healthy,
+}
+
+#[derive(serde::Serialize)]
+struct BootStateEvent {
+    state: BootState,
+    elapsed_ms: u64,
+    /// HTTP status of the health check, once the server answers.
+    status: Option<u16>,
+    /// Start of the response body, for `server_error`.
+    detail: Option<String>,
+}
+
+/// Record `state`, emitting `engine://boot-state` only when it changed.
+fn set_boot_state(
+    app: &AppHandle,
+    current: &mut Option<BootState>,
+    state: BootState,
+    start: Instant,
+    status: Option<u16>,
+    body: &str,
+) {
+    if *current == Some(state) {
+        return;
+    }
+    *current = Some(state);
+    let detail = (state == BootState::ServerError).then(|| body.chars().take(500).collect());
+    if let Some(detail) = &detail {
+        eprintln!(
+            "[SOLAT] WARNING: Engine is up but /health returned HTTP {}: {}",
+            status.unwrap_or_default(),
+            detail
+        );
+    }
+    let event = BootStateEvent {
+        state,
+        elapsed_ms: telemetry::millis(start.elapsed()),
+        status,
+        detail,
+    };
+    let _ = app.emit(BOOT_STATE_EVENT, &event);
+}
+
+/// What a boot is busy with, judged from its log until the server is up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum BootPhase {
+    /// uv is resolving, downloading or installing packages.
+    InstallingDeps,
+    /// The engine process is importing and running its startup.
+    StartingServer,
+    /// uvicorn is listening; the health check decides the rest.
+    WaitingHealth,
+}
+
+#[derive(serde::Serialize)]
+struct BootPhaseEvent {
+    phase: BootPhase,
+    elapsed_ms: u64,
+}
+
+/// Lines uv prints while it is still getting dependencies in place.
+const UV_PROGRESS_MARKERS: [&str; 8] = [
+    "Resolved ",
+    "Downloading ",
+    "Downloaded ",
+    "Prepared ",
+    "Installed ",
+    "Uninstalled ",
+    "Building ",
+    "Creating virtual environment",
+];
+/// Lines uvicorn prints once the engine process itself is starting.
+const SERVER_START_MARKERS: [&str; 2] =
+    ["Started server process", "Waiting for application startup"];
+
+/// The phase the latest recognisable line of `tail` belongs to.
+fn boot_phase_from_log(tail: &str) -> BootPhase {
+    tail.lines()
+        .rev()
+        .find_map(|line| {
+            if SERVER_START_MARKERS
+                .iter()
+                .any(|marker| line.contains(marker))
+            {
+                Some(BootPhase::StartingServer)
+            } else if UV_PROGRESS_MARKERS
+                .iter()
+                .any(|marker| line.contains(marker))
+            {
+                Some(BootPhase::InstallingDeps)
+            } else {
+                None
+            }
+        })
+        .unwrap_or(BootPhase::StartingServer)
+}
+
+/// Record `phase`, emitting `engine://boot-phase` only when it changed.
+fn set_boot_phase(
+    app: &AppHandle,
+    current: &mut Option<BootPhase>,
+    phase: BootPhase,
+    start: Instant,
+) {
+    if *current == Some(phase) {
+        return;
+    }
+    *current = Some(phase);
+    println!("[SOLAT] Boot phase: {:?}", phase);
+    let event = BootPhaseEvent {
+        phase,
+        elapsed_ms: telemetry::millis(start.elapsed()),
+    };
+    let _ = app.emit(BOOT_PHASE_EVENT, &event);
+}
+
+/// Where a just-spawned engine should come up, for `await_boot`.
+struct BootTarget {
+    port: u16,
+    addr: SocketAddr,
+    start: Instant,
+}
+
+/// Kill stale port occupant and spawn the engine, for `start_managed` to
+/// store.
+fn spawn_for_boot(
+    app: &AppHandle,
+    log_path: &Path,
+    config: &EngineConfig,
+) -> Result<(Child, BootTarget), EngineError> {
+    let port = select_port(app, config)?;
+    // Resolve before spawning, so a bad host fails cleanly instead of
+    // leaving an engine we can never see become healthy
+    let addr = engine_addr(app, config)?;
+    ensure_port_free(&app.state::<http::HttpClient>().0, port)?;
+    let start = Instant::now();
+    telemetry::emit(
+        app,
+        config,
+        TelemetryEvent::SpawnAttempt {
+            safe_mode: config.safe_mode,
+        },
+    );
+    let profile_output = prepare_profile_output(app, config);
+    let model = app.state::<ActiveModel>().0.lock_or_recover().clone();
+    let child = spawn_engine(
+        log_path,
+        config,
+        port,
+        profile_output.as_deref(),
+        model.as_deref(),
+        &app.state::<MemoryLog>(),
+    )
+    .inspect_err(|e| {
+        telemetry::emit(
+            app,
+            config,
+            TelemetryEvent::SpawnFailure {
+                reason: e.to_string(),
+                duration_ms: telemetry::millis(start.elapsed()),
+            },
+        );
+    })?;
+    Ok((child, BootTarget { port, addr, start }))
+}
+
+/// Wait for the stored child `pid` to pass its health check. One that fails
+/// to boot is taken back out of `EngineProcess`; one that's merely slow is
+/// kept, and the splash screen keeps polling.
+fn await_boot(
+    app: &AppHandle,
+    log_path: &Path,
+    config: &EngineConfig,
+    pid: u32,
+    target: BootTarget,
+) -> Result<(), EngineError> {
+    let BootTarget { port, addr, start } = target;
+    println!(
+        "[SOLAT] Engine spawned (pid {}), waiting for health...",
+        pid
+    );
+    let stats = app.state::<EngineStats>();
+    stats.boot_started(start);
+
+    let outcome = wait_for_health(
+        app,
+        config,
+        addr,
+        start,
+        health_wait(config),
+        log_path,
+        || match app.state::<EngineProcess>().0.lock_or_recover().as_mut() {
+            Some(child) if child.id() == pid => child.try_wait().map_err(|e| e.to_string()),
+            _ => Err("the engine is no longer managed".to_string()),
+        },
+    );
+    stats.boot_finished(matches!(outcome, HealthOutcome::Healthy));
+    match outcome {
+        HealthOutcome::Healthy => {
+            println!(
+                "[SOLAT] Engine healthy after {:.1}s",
+                start.elapsed().as_secs_f64()
+            );
+            telemetry::emit(
+                app,
+                config,
+                TelemetryEvent::SpawnSuccess {
+                    pid,
+                    duration_ms: telemetry::millis(start.elapsed()),
+                    health_confirmed: true,
+                },
+            );
+            announce_ready(app, port, start);
+            Ok(())
+        }
+        HealthOutcome::Exited(status) => {
+            take_child(app, pid);
+            remove_pid_file(config);
+            let tail = logs::read_log_tail(log_path, 20);
+            let status = process::explain_exit(status, config.memory_limit_mb, &tail);
+            let reason = format!("Engine exited immediately with status: {}", status);
+            telemetry::emit(
+                app,
+                config,
+                TelemetryEvent::SpawnFailure {
+                    reason: reason.clone(),
+                    duration_ms: telemetry::millis(start.elapsed()),
+                },
+            );
+            announce_boot_failed(app, &reason, &tail);
+            Err(format!("{}.\nLast log lines:\n{}", reason, tail).into())
+        }
+        HealthOutcome::FirewallSuspected(detail) => {
+            let error = EngineError::firewall_suspected(port, detail);
+            telemetry::emit(
+                app,
+                config,
+                TelemetryEvent::SpawnFailure {
+                    reason: "firewall suspected".to_string(),
+                    duration_ms: telemetry::millis(start.elapsed()),
+                },
+            );
+            if let Some(mut child) = take_child(app, pid) {
+                let _ = shutdown_child(app, &mut child, STOP_GRACE);
+            }
+            remove_pid_file(config);
+            announce_boot_failed(app, &error.to_string(), &logs::read_log_tail(log_path, 20));
+            Err(error)
+        }
+        HealthOutcome::Unknown(e) => Err(format!("Failed to check engine status: {}", e).into()),
+        HealthOutcome::TimedOut => {
+            // Timeout — engine is still running but not healthy
+            let tail = logs::read_log_tail(log_path, 20);
+            eprintln!(
+                "[SOLAT] WARNING: Engine pid {} not healthy after {}s. Log tail:\n{}",
+                pid,
+                start.elapsed().as_secs(),
+                tail
+            );
+            telemetry::emit(
+                app,
+                config,
+                TelemetryEvent::HealthTimeout {
+                    pid,
+                    duration_ms: telemetry::millis(start.elapsed()),
+                },
+            );
+            announce_boot_failed(
+                app,
+                &format!("Engine not healthy after {}s", start.elapsed().as_secs()),
+                &tail,
+            );
+            // Keep the child anyway — splash screen will keep polling
+            Ok(())
+        }
+    }
+}
+
+/// A launch as `start_managed` left it: the op still held, what `stop_old`
+/// returned, and the stored child's pid alongside what `spawn` returned.
+struct Managed<'a, T, S> {
+    _op: LifecycleOp<'a>,
+    stopped: T,
+    pid: u32,
+    spawned: S,
+}
+
+/// The part of a launch that decides which child ends up managed. Under
+/// `lifecycle` (held on in the result, for the caller's health wait), the
+/// managed child is taken out and handed to `stop_old`, then the one `spawn`
+/// starts is stored. It's stored straight away, with the process lock then
+/// released, so status reads aren't held up by the health wait.
+fn start_managed<'a, T, S>(
+    lifecycle: &'a LifecycleLock,
+    proc_state: &EngineProcess,
+    operation: &str,
+    stop_old: impl FnOnce(Option<Child>) -> Result<T, EngineError>,
+    spawn: impl FnOnce() -> Result<(Child, S), EngineError>,
+) -> Result<Managed<'a, T, S>, EngineError> {
+    let op = lifecycle.begin(operation)?;
+    let old = proc_state.0.lock_or_recover().take();
+    let stopped = stop_old(old)?;
+    let (child, spawned) = spawn()?;
+    let pid = child.id();
+    *proc_state.0.lock_or_recover() = Some(child);
+    Ok(Managed {
+        _op: op,
+        stopped,
+        pid,
+        spawned,
+    })
+}
+
+/// Take the managed child back out if it's still the one spawned as `pid`.
+fn take_child(app: &AppHandle, pid: u32) -> Option<Child> {
+    let proc_state = app.state::<EngineProcess>();
+    let mut guard = proc_state.0.lock_or_recover();
+    if guard.as_ref().map(Child::id) == Some(pid) {
+        guard.take()
+    } else {
+        None
+    }
+}
+
+/// How a boot-time wait for `/health` ended.
+enum HealthOutcome {
+    Healthy,
+    /// The engine exited before becoming healthy.
+    Exited(ExitStatus),
+    /// The port is open but health checks get no HTTP answer, with the
+    /// last transport error.
+    FirewallSuspected(String),
+    /// Still running, but not healthy by the deadline.
+    TimedOut,
+    /// The child's state couldn't be checked (or it is no longer managed).
+    Unknown(String),
+}
+
+/// How long a boot gets to pass its health check: `SOLAT_HEALTH_WAIT_SECS`,
+/// else the default for the launch mode.
+fn health_wait(config: &EngineConfig) -> Duration {
+    let default = if config.safe_mode {
+        SAFE_MODE_HEALTH_WAIT_SECS
+    } else {
+        HEALTH_WAIT_SECS
+    };
+    let secs = match std::env::var("SOLAT_HEALTH_WAIT_SECS") {
+        Ok(value) => match value.trim().parse::<u64>() {
+            Ok(secs) if secs > 0 => secs,
+            _ => {
+                eprintln!(
+                    "[SOLAT] WARNING: Ignoring SOLAT_HEALTH_WAIT_SECS={} (expected a positive number of seconds)",
+                    value
+                );
+                default
+            }
+        },
+        Err(_) => default,
+    };
+    Duration::from_secs(secs)
+}
+
+/// First gap between health polls; each later gap is 1.5x the last, up to
+/// `HEALTH_POLL_MAX`, so a fast boot is seen quickly and a slow one isn't
+/// polled needlessly often.
+const HEALTH_POLL_INITIAL: Duration = Duration::from_millis(100);
+const HEALTH_POLL_MAX: Duration = Duration::from_secs(1);
+
+/// Poll `/health` on `addr` until it passes, `child_status` reports an exit,
+/// or `deadline` after `start` passes, announcing boot progress on the way.
+/// While the boot log at `log_path` keeps growing before the server is up,
+/// the deadline stretches (up to `HEALTH_WAIT_CEILING`), so a first boot
+/// installing dependencies isn't given up on mid-install.
+fn wait_for_health(
+    app: &AppHandle,
+    config: &EngineConfig,
+    addr: SocketAddr,
+    start: Instant,
+    deadline: Duration,
+    log_path: &Path,
+    mut child_status: impl FnMut() -> Result<Option<ExitStatus>, String>,
+) -> HealthOutcome {
+    let client = &app.state::<http::HttpClient>().0;
+    let mut unanswered = 0;
+    let mut boot_state = None;
+    let mut boot_phase = None;
+    let mut poll_delay = HEALTH_POLL_INITIAL;
+    let log_len = || std::fs::metadata(log_path).map(|meta| meta.len()).ok();
+    let mut last_log_len = log_len();
+    let mut last_progress = start;
+    let mut extended = false;
+    // Once uvicorn is up the log grows from our own polls' access lines,
+    // which say nothing about progress
+    let mut serving = false;
+
+    loop {
+        let current_len = log_len();
+        if current_len != last_log_len {
+            last_log_len = current_len;
+            if !serving {
+                last_progress = Instant::now();
+            }
+        }
+        if start.elapsed() >= deadline {
+            if start.elapsed() >= HEALTH_WAIT_CEILING
+                || last_progress.elapsed() >= LOG_PROGRESS_GRACE
+            {
+                break;
+            }
+            if !extended {
+                println!(
+                    "[SOLAT] Engine not healthy after {}s but still logging; waiting longer",
+                    deadline.as_secs()
+                );
+                extended = true;
+            }
+        }
+
+        // Check if child exited early
+        match child_status() {
+            Ok(Some(status)) => return HealthOutcome::Exited(status),
+            Ok(None) => {} // still running, good
+            Err(e) => return HealthOutcome::Unknown(e),
+        }
+
+        // Check if health endpoint responds
+        if !port_accepts(addr) {
+            let phase = boot_phase_from_log(&logs::read_log_tail(log_path, 20));
+            set_boot_phase(app, &mut boot_phase, phase, start);
+            set_boot_state(
+                app,
+                &mut boot_state,
+                BootState::WaitingForServer,
+                start,
+                None,
+                "",
+            );
+        } else {
+            serving = true;
+            set_boot_phase(app, &mut boot_phase, BootPhase::WaitingHealth, start);
+            // Port is open — try an actual HTTP health check
+            let (status, body, json) = match http::block_on(http::check_health(client, addr)) {
+                Ok(response) => {
+                    unanswered = 0;
+                    (Some(response.status), response.body, response.json)
+                }
+                // The connection was accepted but no HTTP response came back
+                Err(detail) => {
+                    unanswered += 1;
+                    if unanswered >= FIREWALL_SUSPECT_ATTEMPTS {
+                        eprintln!(
+                            "[SOLAT] WARNING: Port {} is open but {} health checks got no HTTP response ({}); suspecting a firewall",
+                            addr.port(), unanswered, detail
+                        );
+                        return HealthOutcome::FirewallSuspected(detail);
+                    }
+                    (None, String::new(), None)
+                }
+            };
+            if !body.trim().is_empty() {
+                let event = BootHealthEvent {
+                    elapsed_ms: telemetry::millis(start.elapsed()),
+                    body: json.unwrap_or_else(|| serde_json::Value::String(body.clone())),
+                };
+                let _ = app.emit(BOOT_HEALTH_EVENT, &event);
+            }
+            let healthy =
+                matches!(status, Some(200..=299)) && health::body_is_healthy(config, &body);
+            let state = match status {
+                None => None,
+                Some(_) if healthy => Some(BootState::Healthy),
+                Some(500..=599) => Some(BootState::ServerError),
+                Some(_) => Some(BootState::Initializing),
+            };
+            if let Some(state) = state {
+                set_boot_state(app, &mut boot_state, state, start, status, &body);
+            }
+            if healthy {
+                return HealthOutcome::Healthy;
+            }
+        }
+
+        std::thread::sleep(poll_delay);
+        poll_delay = poll_delay.mul_f64(1.5).min(HEALTH_POLL_MAX);
+    }
+    HealthOutcome::TimedOut
+}
+
+#[derive(serde::Serialize)]
+struct EngineReadyEvent {
+    port: u16,
+    boot_time_ms: u64,
+}
+
+#[derive(serde::Serialize)]
+struct BootFailedEvent {
+    reason: String,
+    log_tail: String,
+}
+
+fn announce_ready(app: &AppHandle, port: u16, start: Instant) {
+    let event = EngineReadyEvent {
+        port,
+        boot_time_ms: telemetry::millis(start.elapsed()),
+    };
+    if let Err(e) = app.emit(ENGINE_READY_EVENT, &event) {
+        eprintln!("[SOLAT] WARNING: Failed to emit engine ready: {}", e);
+    }
+}
+
+fn announce_boot_failed(app: &AppHandle, reason: &str, log_tail: &str) {
+    let event = BootFailedEvent {
+        reason: reason.to_string(),
+        log_tail: log_tail.to_string(),
+    };
+    if let Err(e) = app.emit(BOOT_FAILED_EVENT, &event) {
+        eprintln!("[SOLAT] WARNING: Failed to emit boot failure: {}", e);
+    }
+}
+
+/// Wait in the background for the engine spawned at startup to become
+/// healthy, announcing `engine://ready` or `engine://boot-failed`, so the
+/// splash screen can listen instead of polling.
+fn spawn_boot_watch(app: AppHandle, config: EngineConfig, log_path: PathBuf, start: Instant) {
+    std::thread::spawn(move || {
+        let addr = match engine_addr(&app, &config) {
+            Ok(addr) => addr,
+            Err(e) => {
+                announce_boot_failed(&app, &e, "");
+                return;
+            }
+        };
+        let stats = app.state::<EngineStats>();
+        stats.boot_started(start);
+        let outcome = wait_for_health(
+            &app,
+            &config,
+            addr,
+            start,
+            health_wait(&config),
+            &log_path,
+            || {
+                let proc_state = app.state::<EngineProcess>();
+                let mut guard = proc_state.0.lock_or_recover();
+                match guard.as_mut() {
+                    Some(child) => child.try_wait().map_err(|e| e.to_string()),
+                    None => Err("the engine was stopped".to_string()),
+                }
+            },
+        );
+        stats.boot_finished(matches!(outcome, HealthOutcome::Healthy));
+        let tail = || logs::read_log_tail(&log_path, 20);
+        match outcome {
+            HealthOutcome::Healthy => {
+                println!(
+                    "[SOLAT] Engine healthy after {:.1}s",
+                    start.elapsed().as_secs_f64()
+                );
+                announce_ready(&app, addr.port(), start);
+            }
+            HealthOutcome::Exited(status) => {
+                let tail = tail();
+                let status = process::explain_exit(status, config.memory_limit_mb, &tail);
+                announce_boot_failed(
+                    &app,
+                    &format!("Engine exited immediately with status: {}", status),
+                    &tail,
+                );
+            }
+            HealthOutcome::FirewallSuspected(detail) => {
+                let error = EngineError::firewall_suspected(addr.port(), detail);
+                announce_boot_failed(&app, &error.to_string(), &tail());
+            }
+            HealthOutcome::TimedOut => announce_boot_failed(
+                &app,
+                &format!("Engine not healthy after {}s", start.elapsed().as_secs()),
+                &tail(),
+            ),
+            // Stopped or replaced mid-boot: whoever did that reports on it
+            HealthOutcome::Unknown(_) => {}
+        }
+    });
+}
+
+/// `SOLAT_BLOCKING_START=1` startup for headless/CI runs: start the engine
+/// and wait for health before any window exists, failing (and so aborting
+/// the launch) if it doesn't come up.
+fn start_engine_blocking(
+    app: &AppHandle,
+    log_path: &Path,
+    config: &EngineConfig,
+) -> Result<(), EngineError> {
+    println!("[SOLAT] Blocking start: waiting for engine health before opening the window");
+    let start = Instant::now();
+    let managed = start_managed(
+        app.state::<LifecycleLock>().inner(),
+        app.state::<EngineProcess>().inner(),
+        "start",
+        |_| Ok(()),
+        || spawn_for_boot(app, log_path, config),
+    )?;
+    let pid = managed.pid;
+    record_engine_start(app, config, pid);
+    await_boot(app, log_path, config, pid, managed.spawned)?;
+    if !probe_health(app, config).0 {
+        if let Some(mut child) = take_child(app, pid) {
+            let _ = shutdown_child(app, &mut child, STOP_GRACE);
+        }
+        remove_pid_file(config);
+        return Err(EngineError::HealthTimeout {
+            waited_secs: start.elapsed().as_secs(),
+            log_tail: logs::read_log_tail(log_path, 20),
+        });
+    }
+    Ok(())
+}
+
+/// Build the windows from tauri.conf.json. They're marked `create: false`
+/// so a blocking start can hold them back until the engine is healthy.
+fn create_windows(app: &tauri::App) -> tauri::Result<()> {
+    for window_config in &app.config().app.windows {
+        tauri::WebviewWindowBuilder::from_config(app.handle(), window_config)?.build()?;
+    }
+    Ok(())
+}
+
+/// Stop and reap `child`, giving it `grace` to exit on its own. A profiled
+/// engine is interrupted first so py-spy can write its output, which is then
+/// announced to the frontend.
+fn shutdown_child(app: &AppHandle, child: &mut Child, grace: Duration) -> std::io::Result<()> {
+    let profile_output = app.state::<ProfileOutput>().0.lock_or_recover().take();
+    if profile_output.is_some() {
+        profiling::interrupt(child);
+    }
+    let stopped = process::stop_engine_gracefully(child, grace);
+    if let Some(output) = profile_output {
+        profiling::announce(app, &output);
+    }
+    stopped
+}
+
+/// Stop an adopted engine, force-killing it if `grace` runs out.
+fn stop_adopted_engine(pid: u32, grace: Duration) -> Result<(), String> {
+    if !process::stop_pid_gracefully(pid, grace) {
+        eprintln!(
+            "[SOLAT] WARNING: Adopted engine pid {} did not exit within {:.1}s, killing it",
+            pid,
+            grace.as_secs_f64()
+        );
+        force_kill_pid(pid).map_err(|e| format!("Failed to kill engine: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Stop and reap the managed child, if any. Shared by `stop_engine` and the
+/// process shutdown paths. An adopted engine is only stopped when
+/// `stop_adopted` is set (by `stop_engine`): on exit it keeps running with
+/// its PID file, so the next launch adopts it again. Returns whether an
+/// engine was stopped.
+fn terminate_engine(app: &AppHandle, grace: Duration, stop_adopted: bool) -> Result<bool, String> {
+    let proc_state = app.state::<EngineProcess>();
+    let mut guard = proc_state.0.lock_or_recover();
     if let Some(ref mut child) = *guard {
-        child
-            .kill()
-            .map_err(|e| format!("Failed to kill engine: {}", e))?;
-        let _ = child.wait();
+        shutdown_child(app, child, grace).map_err(|e| format!("Failed to kill engine: {}", e))?;
         *guard = None;
+    } else {
+        let adopted = app.state::<AdoptedEngine>().0.lock_or_recover().take();
+        match adopted {
+            Some(pid) if stop_adopted => stop_adopted_engine(pid, grace)?,
+            Some(pid) => {
+                println!("[SOLAT] Leaving adopted engine (pid {}) running", pid);
+                return Ok(false);
+            }
+            None => return Ok(false),
+        }
+    }
+    remove_pid_file(&running_config(app));
+    Ok(true)
+}
+
+/// Liveness of the engine: the managed child, else an adopted engine.
+fn engine_liveness(app: &AppHandle, child: &mut Option<Child>) -> (bool, Option<u32>) {
+    if child.is_some() {
+        return child_liveness(child);
+    }
+    let adopted = *app.state::<AdoptedEngine>().0.lock_or_recover();
+    match adopted {
+        Some(pid) => (process::pid_alive(pid), Some(pid)),
+        None => (false, None),
+    }
+}
+
+/// Adopt a SOLAT engine already serving on the port the PID file records, or
+/// else the configured one, if it passes `/health`, rather than killing it and
+/// booting a fresh one, returning the port. The recorded port is tried first
+/// as an earlier launch may have fallen back off a taken configured port. Its pid
+/// comes from the PID file when that matches a listener, else from the
+/// listener itself. A PID file whose process is gone is removed.
+///
+/// An engine an earlier launcher spawned with piped output (the default; see
+/// `memory_log`) isn't adopted: its pipes' reader died with that launcher, so
+/// all its output is lost and Python `print`s fail with a broken pipe. It's
+/// killed and the launch replaces it. Only engines writing straight to a
+/// terminal (`log_target: inherit`) or started by hand can be adopted.
+fn adopt_running_engine(app: &AppHandle, config: &EngineConfig) -> Option<u16> {
+    let recorded = match read_pid_file(config) {
+        Some(record) if !process::pid_alive(record.pid) => {
+            remove_pid_file(config);
+            None
+        }
+        recorded => recorded,
+    };
+    let recorded_port = recorded.as_ref().and_then(|record| record.port);
+    // An auto port isn't known until a launch picks one
+    let configured_port = match config.port {
+        PortSetting::Fixed(port) => Some(port),
+        PortSetting::Auto => None,
+    };
+    let client = &app.state::<http::HttpClient>().0;
+    let port = recorded_port
+        .into_iter()
+        .chain(configured_port)
+        .find(|&port| is_our_engine(client, port))?;
+    *app.state::<EnginePort>().0.lock_or_recover() = port;
+    if !probe_health(app, config).0 {
+        return None;
+    }
+    let listeners = pids_on_port(port).unwrap_or_default();
+    let recorded_pid = recorded.as_ref().map(|record| record.pid);
+    let pid = match recorded_pid {
+        Some(pid) if listeners.contains(&pid) => pid,
+        _ => listeners.first().copied().or(recorded_pid)?,
+    };
+    if recorded_pid == Some(pid) {
+        if recorded.is_some_and(|record| record.piped_output) {
+            println!(
+                "[SOLAT] Engine pid {} on port {} lost its output pipe with the launcher that started it; replacing it",
+                pid, port
+            );
+            // Off the configured port, no launch would ever free it
+            if let Err(e) = kill_pid_on_port(port) {
+                eprintln!("[SOLAT] WARNING: {}", e);
+            }
+            remove_pid_file(config);
+            return None;
+        }
+    } else {
+        write_pid_file(config, pid, port, false);
+    }
+    println!(
+        "[SOLAT] Reusing healthy engine pid {} already serving on port {} (managed externally)",
+        pid, port
+    );
+    *app.state::<AdoptedEngine>().0.lock_or_recover() = Some(pid);
+    *app.state::<LaunchedConfig>().0.lock_or_recover() = Some(config.clone());
+    Some(port)
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// `memory_limit_mb` of the current (or last) launch, which is what an exit
+/// has to be explained against; the config may have changed since.
+fn launched_memory_limit(app: &AppHandle) -> u64 {
+    app.state::<LaunchedConfig>()
+        .0
+        .lock_or_recover()
+        .as_ref()
+        .map_or(0, |config| config.memory_limit_mb)
+}
+
+#[derive(Debug, serde::Serialize)]
+struct Relaunch {
+    /// The managed child that was replaced, if there was one.
+    old_pid: Option<u32>,
+    new_pid: u32,
+    /// From stopping the old engine to the new one passing its health check
+    /// (or the health wait giving up).
+    boot_time_ms: u64,
+}
+
+/// Replace any managed child with a freshly started engine. The log path is
+/// re-derived from `config` so a changed data dir takes effect here.
+///
+/// Replacing an existing child is recorded in the restart history under
+/// `reason`, which also names the operation in the `Busy` error a
+/// concurrent lifecycle operation gets.
+fn launch_engine(
+    app: &AppHandle,
+    proc_state: &EngineProcess,
+    log_state: &EngineLogPath,
+    config: &EngineConfig,
+    reason: &str,
+) -> Result<String, EngineError> {
+    relaunch_engine(app, proc_state, log_state, config, reason)
+        .map(|relaunch| format!("Engine started (pid {})", relaunch.new_pid))
+}
+
+/// `launch_engine`, reporting both pids. The `LifecycleLock` is held
+/// throughout (see `start_managed`), so launches from commands, the
+/// watchdog and the scheduler can't interleave.
+fn relaunch_engine(
+    app: &AppHandle,
+    proc_state: &EngineProcess,
+    log_state: &EngineLogPath,
+    config: &EngineConfig,
+    reason: &str,
+) -> Result<Relaunch, EngineError> {
+    let previous_log = log_state.0.lock_or_recover().clone();
+    let log_path = logs::engine_log_path(&resolve_data_dir(config));
+    let start = Instant::now();
+
+    let managed = start_managed(
+        app.state::<LifecycleLock>().inner(),
+        proc_state,
+        reason,
+        |old| stop_replaced_engine(app, old, &previous_log, reason),
+        || {
+            *log_state.0.lock_or_recover() = log_path.clone();
+            spawn_for_boot(app, &log_path, config)
+        },
+    )?;
+    let (old_pid, crashed_status) = managed.stopped;
+    let new_pid = managed.pid;
+    record_engine_start(app, config, new_pid);
+    await_boot(app, &log_path, config, new_pid, managed.spawned)?;
+    let boot_time_ms = telemetry::millis(start.elapsed());
+
+    if let Some(exit_status) = crashed_status {
+        telemetry::emit(
+            app,
+            config,
+            TelemetryEvent::CrashRestart {
+                exit_status,
+                duration_ms: telemetry::millis(start.elapsed()),
+            },
+        );
+    }
+
+    Ok(Relaunch {
+        old_pid,
+        new_pid,
+        boot_time_ms,
+    })
+}
+
+/// Stop the engine a launch replaces: the managed child `old`, else an
+/// adopted engine. A replacement goes in the restart history under `reason`.
+/// Returns the stopped pid and, if the child had already crashed, how it
+/// exited.
+fn stop_replaced_engine(
+    app: &AppHandle,
+    old: Option<Child>,
+    previous_log: &Path,
+    reason: &str,
+) -> Result<(Option<u32>, Option<String>), EngineError> {
+    let old_pid = old.as_ref().map(Child::id);
+    let mut crashed_status = None;
+    if let Some(mut child) = old {
+        if let Ok(Some(status)) = child.try_wait() {
+            let tail = logs::read_log_tail(previous_log, supervisor::LOG_EXCERPT_LINES);
+            crashed_status = Some(process::explain_exit(
+                status,
+                launched_memory_limit(app),
+                &tail,
+            ));
+        }
+        let _ = shutdown_child(app, &mut child, STOP_GRACE);
+    }
+    let adopted = app.state::<AdoptedEngine>().0.lock_or_recover().take();
+    if let Some(pid) = adopted {
+        stop_adopted_engine(pid, STOP_GRACE)?;
+    }
+    let old_pid = old_pid.or(adopted);
+    if old_pid.is_some() {
+        // From the old launch's data dir, which the new one may not share
+        remove_pid_file(&running_config(app));
+        // Capture the excerpt now: the new spawn reopens the log
+        app.state::<RestartHistory>().record(RestartRecord {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            reason: reason.to_string(),
+            exit_status: crashed_status.clone(),
+            log_excerpt: logs::read_log_tail(previous_log, supervisor::LOG_EXCERPT_LINES),
+        });
+    }
+    Ok((old_pid, crashed_status))
+}
+
+/// Stop the engine (gracefully) and start it again in one step, so the UI
+/// doesn't race port cleanup between `stop_engine` and `start_engine`.
+#[tauri::command]
+async fn restart_engine(
+    app: AppHandle,
+    proc_state: tauri::State<'_, EngineProcess>,
+    log_state: tauri::State<'_, EngineLogPath>,
+    config_state: tauri::State<'_, EngineConfigState>,
+) -> Result<Relaunch, EngineError> {
+    let config = config_state.current();
+    relaunch_engine(&app, &proc_state, &log_state, &config, "manual restart")
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct EngineStarted {
+    message: String,
+    metrics: EngineMetrics,
+}
+
+#[tauri::command]
+async fn start_engine(
+    app: AppHandle,
+    proc_state: tauri::State<'_, EngineProcess>,
+    log_state: tauri::State<'_, EngineLogPath>,
+    config_state: tauri::State<'_, EngineConfigState>,
+) -> Result<EngineStarted, EngineError> {
+    let config = config_state.current();
+    let message = launch_engine(&app, &proc_state, &log_state, &config, "start")?;
+    Ok(EngineStarted {
+        message,
+        metrics: app.state::<EngineStats>().metrics(),
+    })
+}
+
+/// Start the engine in safe mode regardless of the configured setting, so the
+/// UI can offer "try safe mode" after a crash without editing the config.
+#[tauri::command]
+async fn start_engine_safe(
+    app: AppHandle,
+    proc_state: tauri::State<'_, EngineProcess>,
+    log_state: tauri::State<'_, EngineLogPath>,
+    config_state: tauri::State<'_, EngineConfigState>,
+) -> Result<String, EngineError> {
+    let mut config = config_state.current();
+    config.safe_mode = true;
+    launch_engine(&app, &proc_state, &log_state, &config, "safe mode restart")
+}
+
+/// Start the engine under the profiler regardless of the configured setting.
+/// The profile path arrives as `engine://profile-ready` once it stops.
+#[tauri::command]
+async fn start_engine_profiled(
+    app: AppHandle,
+    proc_state: tauri::State<'_, EngineProcess>,
+    log_state: tauri::State<'_, EngineLogPath>,
+    config_state: tauri::State<'_, EngineConfigState>,
+) -> Result<String, EngineError> {
+    let mut config = config_state.current();
+    config.profile = true;
+    launch_engine(&app, &proc_state, &log_state, &config, "profiled restart")
+}
+
+/// `post_stop_hook`, with its output appended to the stopped engine's log.
+fn run_post_stop_hook(app: &AppHandle) -> Result<(), String> {
+    let config = app.state::<EngineConfigState>().current();
+    if config.post_stop_hook.is_empty() {
+        return Ok(());
+    }
+    let engine_dir = require_engine_dir().map_err(|e| e.to_string())?;
+    let log_path = app.state::<EngineLogPath>().0.lock_or_recover().clone();
+    let mut log = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)
+        .map_err(|e| format!("Failed to open log file: {}", e))?;
+    hooks::run(
+        "post-stop hook",
+        &config.post_stop_hook,
+        &engine_dir,
+        Some(&mut log),
+    )
+    .map_err(|e| format!("Engine stopped, but {}", e))
+}
+
+/// Stop the engine, giving it `grace_ms` (default `STOP_GRACE`) to shut down
+/// cleanly before it's killed; 0 kills it straight away.
+#[tauri::command]
+async fn stop_engine(
+    app: AppHandle,
+    lifecycle: tauri::State<'_, LifecycleLock>,
+    grace_ms: Option<u64>,
+) -> Result<String, EngineError> {
+    let _op = lifecycle.begin("stop")?;
+    let grace = grace_ms.map_or(STOP_GRACE, Duration::from_millis);
+    if terminate_engine(&app, grace, true)? {
+        run_post_stop_hook(&app)?;
         Ok("Engine stopped".to_string())
     } else {
         Ok("No engine process to stop".to_string())
     }
 }
 
+/// The stored config, without env overrides, so a settings UI that saves it
+/// back through `set_engine_config` doesn't persist them.
+#[tauri::command]
+async fn get_engine_config(
+    config_state: tauri::State<'_, EngineConfigState>,
+) -> Result<EngineConfig, String> {
+    Ok(config_state.0.lock_or_recover().clone())
+}
+
+/// Every setting in effect with its source (default, file, env, or a
+/// per-launch argument), for support and for seeing through the layering.
+#[tauri::command]
+async fn get_effective_config(
+    config_state: tauri::State<'_, EngineConfigState>,
+    launched_state: tauri::State<'_, LaunchedConfig>,
+) -> Result<config::EffectiveConfig, String> {
+    let current = config_state.current();
+    let launched = launched_state.0.lock_or_recover().clone();
+    Ok(config::effective_config(
+        &config::config_path(),
+        &current,
+        &config_state.1,
+        launched.as_ref(),
+    ))
+}
+
+/// Only the settings that differ from the built-in defaults, for support and
+/// for resetting individual settings.
+#[tauri::command]
+async fn diff_config_from_default(
+    config_state: tauri::State<'_, EngineConfigState>,
+) -> Result<Vec<config::ConfigDiff>, String> {
+    let current = config_state.current();
+    Ok(config::diff_from_default(&current))
+}
+
+/// Check a candidate config without persisting or applying it, so the
+/// settings UI can show inline errors while the user edits.
+#[tauri::command]
+async fn validate_engine_config(candidate: EngineConfig) -> Result<Vec<ConfigError>, String> {
+    Ok(config::validate_config(&candidate))
+}
+
+/// Validate and persist a new config. It applies from the next engine start.
+#[tauri::command]
+async fn set_engine_config(
+    config_state: tauri::State<'_, EngineConfigState>,
+    config: EngineConfig,
+) -> Result<(), Vec<ConfigError>> {
+    let errors = config::validate_config(&config);
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    let mut current = config_state.0.lock_or_recover();
+    config::save_config(&config::config_path(), &config).map_err(|e| vec![e])?;
+    *current = config;
+    Ok(())
+}
+
+/// Validate and persist an engine data directory override. The engine picks
+/// it up (as `SOLAT_DATA_DIR`) on its next start, as does the boot log.
+#[tauri::command]
+async fn set_engine_data_dir(
+    config_state: tauri::State<'_, EngineConfigState>,
+    path: String,
+) -> Result<String, ConfigError> {
+    let resolved = config::validate_data_dir(Path::new(&path))?;
+    let mut config = config_state.0.lock_or_recover();
+    let mut updated = config.clone();
+    updated.data_dir = Some(resolved.clone());
+    config::save_config(&config::config_path(), &updated)?;
+    *config = updated;
+    Ok(format!(
+        "Data directory set to {} (takes effect on next engine start)",
+        resolved.display()
+    ))
+}
+
+/// Move the engine to `new_port`: check it's free, restart the engine there,
+/// and once it's up on that port persist it as the fixed port. The new port
+/// is also announced as `engine://port`.
+#[tauri::command]
+async fn restart_on_port(
+    app: AppHandle,
+    proc_state: tauri::State<'_, EngineProcess>,
+    log_state: tauri::State<'_, EngineLogPath>,
+    config_state: tauri::State<'_, EngineConfigState>,
+    new_port: u16,
+) -> Result<EngineStatus, EngineError> {
+    let mut candidate = config_state.0.lock_or_recover().clone();
+    let current_port = *app.state::<EnginePort>().0.lock_or_recover();
+    candidate.port = PortSetting::Fixed(new_port);
+    if let Some(error) = config::validate_config(&candidate).into_iter().next() {
+        return Err(error.to_string().into());
+    }
+    // Our own engine holds the current port; anything on another port isn't ours
+    if new_port != current_port && port_is_occupied(new_port) {
+        return Err(format!("Port {} is already in use", new_port).into());
+    }
+
+    // The requested port wins over SOLAT_ENGINE_PORT for this launch
+    let mut config = config_state.current();
+    config.port = PortSetting::Fixed(new_port);
+    println!(
+        "[SOLAT] Moving engine from port {} to {}",
+        current_port, new_port
+    );
+    launch_engine(&app, &proc_state, &log_state, &config, "port change")?;
+    // Something may have taken the port since the check above, in which
+    // case the launch fell back to another one
+    let launched_port = *app.state::<EnginePort>().0.lock_or_recover();
+    if launched_port != new_port {
+        return Err(format!(
+            "Port {} was taken when the engine restarted; it is running on port {} instead, and the configured port is unchanged",
+            new_port, launched_port
+        )
+        .into());
+    }
+
+    let mut stored = config_state.0.lock_or_recover();
+    let mut updated = stored.clone();
+    updated.port = PortSetting::Fixed(new_port);
+    config::save_config(&config::config_path(), &updated).map_err(|e| {
+        format!(
+            "Engine moved to port {}, but saving it as the configured port failed: {}",
+            new_port, e
+        )
+    })?;
+    *stored = updated;
+    drop(stored);
+
+    let log_path = log_state.0.lock_or_recover().clone();
+    let (running, pid) = engine_liveness(&app, &mut proc_state.0.lock_or_recover());
+    Ok(build_status(&app, running, pid, &log_path, &config, 30))
+}
+
 #[derive(serde::Serialize)]
 struct EngineStatus {
+    app_version: &'static str,
+    git_commit: Option<&'static str>,
+    /// Version the engine reports in its health body.
+    engine_version: Option<String>,
     running: bool,
     pid: Option<u32>,
+    /// Port of the current launch, which the frontend's requests must target.
+    port: u16,
     health_ok: bool,
     health_body: Option<String>,
     health_error: Option<String>,
+    /// `health_body` parsed, when the engine answered with its usual JSON.
+    health: Option<health::HealthReport>,
+    /// Engine has stopped accepting work via `pause_engine`.
+    paused: bool,
+    /// Model the engine reports in its health body, else the one last chosen
+    /// with `switch_model`.
+    active_model: Option<String>,
+    /// `device` the engine was launched with, if one was selected.
+    device: Option<String>,
+    /// `/health` passes but the deep-health canary fails.
+    degraded: bool,
+    /// Latest canary outcome, when `deep_health` is configured.
+    deep_health: Option<canary::CanaryResult>,
+    metrics: EngineMetrics,
+    /// Seconds since the running engine was spawned; one that keeps
+    /// restarting never gets far from zero.
+    uptime_secs: Option<u64>,
+    /// When the running engine was spawned, RFC 3339.
+    started_at: Option<String>,
+    /// The engine was adopted at startup rather than spawned by this session.
+    managed_externally: bool,
     log_tail: String,
     log_path: String,
 }
 
-#[tauri::command]
-async fn get_engine_status(
-    proc_state: tauri::State<'_, EngineProcess>,
-    log_state: tauri::State<'_, EngineLogPath>,
-) -> Result<EngineStatus, String> {
-    let log_path = log_state.0.lock().map_err(|e| e.to_string())?.clone();
-
-    let (running, pid) = {
-        let mut guard = proc_state.0.lock().map_err(|e| e.to_string())?;
-        match &mut *guard {
-            Some(child) => {
-                // Check if still alive
-                match child.try_wait() {
-                    Ok(Some(_status)) => {
-                        // Process has exited
-                        let pid = child.id();
-                        *guard = None;
-                        (false, Some(pid))
-                    }
-                    Ok(None) => (true, Some(child.id())),
-                    Err(_) => (false, None),
-                }
-            }
-            None => (false, None),
-        }
-    };
+/// Liveness of the managed child. An exited child is left in place so the
+/// watchdog still sees (and records) the crash.
+fn child_liveness(child: &mut Option<Child>) -> (bool, Option<u32>) {
+    match child {
+        Some(child) => match child.try_wait() {
+            Ok(Some(_status)) => (false, Some(child.id())),
+            Ok(None) => (true, Some(child.id())),
+            Err(_) => (false, None),
+        },
+        None => (false, None),
+    }
+}
 
-    // Try health check
-    let (health_ok, health_body, health_error) = match StdCommand::new("curl")
-        .args([
-            "-sS",
-            "--max-time",
-            "2",
-            &format!("http://127.0.0.1:{}/health", ENGINE_PORT),
-        ])
-        .output()
-    {
-        Ok(output) => {
-            let body = String::from_utf8_lossy(&output.stdout).to_string();
-            if body.contains("healthy") {
-                (true, Some(body), None)
-            } else {
-                let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-                (false, Some(body), Some(stderr))
-            }
-        }
-        Err(e) => (false, None, Some(e.to_string())),
+/// Probe `/health`, returning `(health_ok, health_body, health_error)`.
+fn probe_health(app: &AppHandle, config: &EngineConfig) -> (bool, Option<String>, Option<String>) {
+    let addr = match engine_addr(app, config) {
+        Ok(addr) => addr,
+        Err(e) => return (false, None, Some(e)),
     };
+    let client = &app.state::<http::HttpClient>().0;
+    match http::block_on(http::check_health(client, addr)) {
+        Ok(response) if !(200..=299).contains(&response.status) => (
+            false,
+            Some(response.body),
+            Some(format!("HTTP {}", response.status)),
+        ),
+        Ok(response) if health::body_is_healthy(config, &response.body) => {
+            (true, Some(response.body), None)
+        }
+        Ok(response) => (
+            false,
+            Some(response.body),
+            Some("Engine answered but does not report healthy".to_string()),
+        ),
+        Err(e) => (false, None, Some(e)),
+    }
+}
+
+fn build_status(
+    app: &AppHandle,
+    running: bool,
+    pid: Option<u32>,
+    log_path: &Path,
+    config: &EngineConfig,
+    tail_lines: usize,
+) -> EngineStatus {
+    let (health_ok, health_body, health_error) = probe_health(app, config);
+    let health_json = health_body
+        .as_deref()
+        .and_then(|body| serde_json::from_str::<serde_json::Value>(body).ok());
+    let health = health_json.as_ref().and_then(health::parse_report);
+    let paused = health_json
+        .as_ref()
+        .and_then(|json| json.get("paused").and_then(|v| v.as_bool()))
+        .unwrap_or(false);
+    let active_model = health_body
+        .as_deref()
+        .and_then(model::reported_model)
+        .or_else(|| app.state::<ActiveModel>().0.lock_or_recover().clone());
+    let deep_health = app.state::<DeepHealthState>().0.lock_or_recover().clone();
+    let degraded = health_ok && deep_health.as_ref().is_some_and(|result| !result.ok);
+    let log_tail = logs::read_log_tail(log_path, tail_lines);
+    let port = *app.state::<EnginePort>().0.lock_or_recover();
+    let device = app
+        .state::<LaunchedConfig>()
+        .0
+        .lock_or_recover()
+        .as_ref()
+        .map_or_else(|| config.device.clone(), |launched| launched.device.clone());
+
+    let managed_externally = app.state::<AdoptedEngine>().0.lock_or_recover().is_some();
 
-    let log_tail = read_log_tail(&log_path, 30);
+    let (uptime_secs, started_at) = app
+        .state::<EngineUptime>()
+        .0
+        .lock_or_recover()
+        .as_ref()
+        .filter(|_| running)
+        .map(|started| (started.at.elapsed().as_secs(), started.timestamp.clone()))
+        .unzip();
 
-    Ok(EngineStatus {
+    EngineStatus {
+        app_version: APP_VERSION,
+        git_commit: GIT_COMMIT,
+        engine_version: health.as_ref().and_then(|report| report.version.clone()),
         running,
         pid,
+        port,
         health_ok,
         health_body,
         health_error,
+        health,
+        paused,
+        active_model,
+        device,
+        degraded,
+        deep_health,
+        metrics: app.state::<EngineStats>().metrics(),
+        uptime_secs,
+        started_at,
+        managed_externally,
         log_tail,
         log_path: log_path.to_string_lossy().to_string(),
+    }
+}
+
+/// Check each launch stage on its own (engine dir, uv, import, port, and
+/// optionally a throwaway spawn) without touching the running engine.
+#[tauri::command]
+async fn run_startup_diagnostics(
+    app: AppHandle,
+    proc_state: tauri::State<'_, EngineProcess>,
+    config_state: tauri::State<'_, EngineConfigState>,
+    port_state: tauri::State<'_, EnginePort>,
+    http_client: tauri::State<'_, http::HttpClient>,
+    dry_spawn: Option<bool>,
+) -> Result<Vec<diagnostics::DiagnosticStep>, String> {
+    let config = config_state.current();
+    let (running, _) = engine_liveness(&app, &mut proc_state.0.lock_or_recover());
+    let port = *port_state.0.lock_or_recover();
+    Ok(diagnostics::run(
+        &http_client.0,
+        &config,
+        running,
+        port,
+        dry_spawn.unwrap_or(false),
+    ))
+}
+
+/// Whether uv, the engine dir, its venv and a recent enough Python are in
+/// place, so first-run problems show as a checklist instead of a log tail.
+#[tauri::command]
+async fn check_prerequisites() -> Result<diagnostics::PrereqReport, String> {
+    Ok(diagnostics::prerequisites())
+}
+
+/// Exactly what uv/python resolution inspected and what it picked, for
+/// launch failures that only happen outside a terminal.
+#[tauri::command]
+async fn get_resolution_diagnostics() -> Result<ResolutionDiagnostics, String> {
+    let mut checked_locations = Vec::new();
+    let found_uv = find_uv(&mut checked_locations);
+    let engine_dir = find_engine_dir();
+    let found_python = find_python(engine_dir.as_deref(), &mut checked_locations);
+    let (login_shell, login_shell_path) = login_shell_path().unzip();
+
+    let explanation = match (&found_uv, &found_python) {
+        (Some(uv), _) => format!("Launches use uv at {}", uv.display()),
+        (None, Some(python)) => format!(
+            "uv was not found in any checked location; launches fall back to {}",
+            python.display()
+        ),
+        (None, None) => {
+            "Neither uv nor python3 was found; launches will fail to spawn. Install uv, or \
+             make sure it is on the PATH of your login shell"
+                .to_string()
+        }
+    };
+    let explanation = if engine_dir.is_none() {
+        format!("{}. The engine directory was not found either", explanation)
+    } else {
+        explanation
+    };
+
+    Ok(ResolutionDiagnostics {
+        path_env: std::env::var("PATH").ok(),
+        login_shell,
+        login_shell_path,
+        checked_locations,
+        found_uv: found_uv.map(|p| p.display().to_string()),
+        found_python: found_python.map(|p| p.display().to_string()),
+        explanation,
+    })
+}
+
+/// Persist uvicorn's log level (e.g. `debug`); takes effect on the next
+/// engine start.
+#[tauri::command]
+async fn set_log_level(
+    config_state: tauri::State<'_, EngineConfigState>,
+    level: String,
+) -> Result<String, ConfigError> {
+    let level = config::parse_log_level(&level).map_err(|message| ConfigError::InvalidField {
+        field: "log_level".to_string(),
+        message,
+    })?;
+    let mut config = config_state.0.lock_or_recover();
+    let mut updated = config.clone();
+    updated.log_level = level.to_string();
+    config::save_config(&config::config_path(), &updated)?;
+    *config = updated;
+    Ok(format!(
+        "Log level set to {} (takes effect on next engine start)",
+        level
+    ))
+}
+
+/// Persist `port` as the fixed engine port, used from the next start. Refused
+/// while the engine runs, since it would keep serving on the old port; use
+/// `restart_on_port` to move a running engine.
+#[tauri::command]
+async fn set_engine_port(
+    app: AppHandle,
+    proc_state: tauri::State<'_, EngineProcess>,
+    config_state: tauri::State<'_, EngineConfigState>,
+    port_state: tauri::State<'_, EnginePort>,
+    port: u16,
+) -> Result<u16, ConfigError> {
+    // An adopted engine serves on the current port just the same
+    let running = engine_liveness(&app, &mut proc_state.0.lock_or_recover()).0;
+    if running {
+        return Err(ConfigError::InvalidField {
+            field: "port".to_string(),
+            message: "can't change the port while the engine is running; stop it first".to_string(),
+        });
+    }
+    let mut config = config_state.0.lock_or_recover();
+    let mut updated = config.clone();
+    updated.port = PortSetting::Fixed(port);
+    if let Some(error) = config::validate_config(&updated).into_iter().next() {
+        return Err(error);
+    }
+    config::save_config(&config::config_path(), &updated)?;
+    *config = updated;
+    *port_state.0.lock_or_recover() = port;
+    println!("[SOLAT] Engine port set to {}", port);
+    Ok(port)
+}
+
+/// Forget the cached uv path and resolve it again, e.g. after installing uv
+/// with the app open. Returns the path found, if any.
+#[tauri::command]
+async fn refresh_uv_path() -> Result<Option<String>, String> {
+    UV_PATH.lock_or_recover().take();
+    Ok(resolve_uv_path().map(|uv| uv.display().to_string()))
+}
+
+/// Port of the current engine launch (fixed, or picked by `port: "auto"`).
+#[tauri::command]
+async fn get_engine_port(port_state: tauri::State<'_, EnginePort>) -> Result<u16, String> {
+    Ok(*port_state.0.lock_or_recover())
+}
+
+#[tauri::command]
+async fn get_engine_status(
+    app: AppHandle,
+    proc_state: tauri::State<'_, EngineProcess>,
+    log_state: tauri::State<'_, EngineLogPath>,
+    config_state: tauri::State<'_, EngineConfigState>,
+) -> Result<EngineStatus, String> {
+    let log_path = log_state.0.lock_or_recover().clone();
+    let config = config_state.current();
+    let (running, pid) = engine_liveness(&app, &mut proc_state.0.lock_or_recover());
+    Ok(build_status(&app, running, pid, &log_path, &config, 30))
+}
+
+/// Open file descriptors (handles on Windows) held by the engine, for
+/// spotting leaks over time. `None` when no engine is running.
+#[tauri::command]
+async fn get_engine_fd_count(
+    proc_state: tauri::State<'_, EngineProcess>,
+) -> Result<Option<usize>, String> {
+    let (running, pid) = child_liveness(&mut proc_state.0.lock_or_recover());
+    Ok(pid.filter(|_| running).and_then(process::open_fd_count))
+}
+
+const FULL_REPORT_LOG_LINES: usize = 50;
+
+#[derive(serde::Serialize)]
+struct ResolvedPaths {
+    engine_dir: Option<String>,
+    uv_path: Option<String>,
+    data_dir: String,
+    log_path: String,
+    config_path: String,
+}
+
+#[derive(serde::Serialize)]
+struct FullReport {
+    /// RFC 3339, UTC.
+    generated_at: String,
+    status: EngineStatus,
+    paths: ResolvedPaths,
+    config: EngineConfig,
+    restart_history: Vec<RestartRecord>,
+}
+
+/// Everything the diagnostics screen needs in one call. Managed state is
+/// copied under a single short-held set of locks so the pieces agree with
+/// each other; the slow probes (health, uv resolution) run after release.
+#[tauri::command]
+async fn get_full_report(
+    app: AppHandle,
+    proc_state: tauri::State<'_, EngineProcess>,
+    log_state: tauri::State<'_, EngineLogPath>,
+    config_state: tauri::State<'_, EngineConfigState>,
+    history: tauri::State<'_, RestartHistory>,
+) -> Result<FullReport, String> {
+    let (running, pid, log_path, config, restart_history) = {
+        let mut child = proc_state.0.lock_or_recover();
+        let log_path = log_state.0.lock_or_recover().clone();
+        let config = config_state.current();
+        let (running, pid) = engine_liveness(&app, &mut child);
+        (running, pid, log_path, config, history.snapshot())
+    };
+
+    let paths = ResolvedPaths {
+        engine_dir: find_engine_dir().map(|p| p.display().to_string()),
+        uv_path: resolve_uv_path().map(|p| p.display().to_string()),
+        data_dir: resolve_data_dir(&config).display().to_string(),
+        log_path: log_path.display().to_string(),
+        config_path: config::config_path().display().to_string(),
+    };
+
+    Ok(FullReport {
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        status: build_status(
+            &app,
+            running,
+            pid,
+            &log_path,
+            &config,
+            FULL_REPORT_LOG_LINES,
+        ),
+        paths,
+        config,
+        restart_history,
     })
 }
 
+/// Time the configured sample request against the engine for a quick
+/// "is my machine fast enough" check.
+#[tauri::command]
+async fn benchmark_engine(
+    app: AppHandle,
+    config_state: tauri::State<'_, EngineConfigState>,
+    iterations: usize,
+) -> Result<http::BenchResult, String> {
+    let config = config_state.current();
+    let client = app.state::<http::HttpClient>().0.clone();
+    http::benchmark(
+        &client,
+        engine_addr(&app, &config)?,
+        &config.sample_request,
+        iterations,
+    )
+    .await
+}
+
+/// Time to the first byte of the sample request's response: a single,
+/// responsiveness-focused probe next to the full benchmark.
+#[tauri::command]
+async fn measure_first_response(
+    app: AppHandle,
+    config_state: tauri::State<'_, EngineConfigState>,
+) -> Result<http::FirstResponse, String> {
+    let config = config_state.current();
+    let client = app.state::<http::HttpClient>().0.clone();
+    http::measure_first_response(&client, engine_addr(&app, &config)?, &config.sample_request).await
+}
+
+/// Check the engine serves every route this app calls, so the UI can warn
+/// that the engine is too old for this app version.
+#[tauri::command]
+async fn check_api_compatibility(
+    app: AppHandle,
+    config_state: tauri::State<'_, EngineConfigState>,
+) -> Result<compat::CompatReport, String> {
+    let config = config_state.current();
+    let client = app.state::<http::HttpClient>().0.clone();
+    compat::check(&client, engine_addr(&app, &config)?).await
+}
+
+/// Cancel one in-flight engine request (by the id its log lines carry)
+/// without stopping the engine.
+#[tauri::command]
+async fn cancel_engine_request(
+    app: AppHandle,
+    config_state: tauri::State<'_, EngineConfigState>,
+    request_id: String,
+) -> Result<http::CancelResult, String> {
+    let config = config_state.current();
+    let client = app.state::<http::HttpClient>().0.clone();
+    http::cancel_request(&client, engine_addr(&app, &config)?, &request_id).await
+}
+
+/// Ask the engine to stop accepting new work without restarting it.
+#[tauri::command]
+async fn pause_engine(
+    app: AppHandle,
+    config_state: tauri::State<'_, EngineConfigState>,
+) -> Result<String, String> {
+    let config = config_state.current();
+    let addr = engine_addr(&app, &config)?;
+    let client = app.state::<http::HttpClient>().0.clone();
+    http::post_admin(&client, addr, "/admin/pause").await?;
+    Ok("Engine paused".to_string())
+}
+
 #[tauri::command]
-async fn get_engine_log(log_state: tauri::State<'_, EngineLogPath>) -> Result<String, String> {
-    let log_path = log_state.0.lock().map_err(|e| e.to_string())?.clone();
-    read_log_tail_full(&log_path).map_err(|e| e.to_string())
+async fn resume_engine(
+    app: AppHandle,
+    config_state: tauri::State<'_, EngineConfigState>,
+) -> Result<String, String> {
+    let config = config_state.current();
+    let addr = engine_addr(&app, &config)?;
+    let client = app.state::<http::HttpClient>().0.clone();
+    http::post_admin(&client, addr, "/admin/resume").await?;
+    Ok("Engine resumed".to_string())
 }
 
-fn read_log_tail_full(path: &PathBuf) -> Result<String, std::io::Error> {
-    let file = fs::File::open(path)?;
-    let reader = BufReader::new(file);
-    let lines: Vec<String> = reader.lines().collect::<Result<_, _>>()?;
-    // Return last 100 lines
-    let start = if lines.len() > 100 {
-        lines.len() - 100
+/// Switch the engine to `model_id`: hot-swapped through `/admin/model` if the
+/// engine supports it, else by restarting it with `SOLAT_MODEL`. Progress
+/// arrives as `engine://model-switch` events.
+#[tauri::command]
+async fn switch_model(
+    app: AppHandle,
+    proc_state: tauri::State<'_, EngineProcess>,
+    log_state: tauri::State<'_, EngineLogPath>,
+    config_state: tauri::State<'_, EngineConfigState>,
+    model_id: String,
+) -> Result<model::ModelSwitch, EngineError> {
+    let model_id = model_id.trim().to_string();
+    if model_id.is_empty() {
+        return Err("Model id must not be empty".to_string().into());
+    }
+    let config = config_state.current();
+    let addr = engine_addr(&app, &config)?;
+    let client = app.state::<http::HttpClient>().0.clone();
+    let start = Instant::now();
+
+    model::emit_progress(&app, &model_id, model::SwitchPhase::Requested, start, None);
+    let body = serde_json::json!({ "model_id": model_id });
+    let hot_swapped = match http::post_admin_json(&client, addr, "/admin/model", Some(&body)).await
+    {
+        Ok(supported) => supported,
+        Err(e) => {
+            model::emit_progress(
+                &app,
+                &model_id,
+                model::SwitchPhase::Failed,
+                start,
+                Some(e.clone()),
+            );
+            return Err(e.into());
+        }
+    };
+
+    let previous = app
+        .state::<ActiveModel>()
+        .0
+        .lock_or_recover()
+        .replace(model_id.clone());
+    let result = if hot_swapped {
+        model::wait_until_loaded(&app, &config, &model_id, start).map_err(EngineError::from)
     } else {
-        0
+        println!(
+            "[SOLAT] Engine has no model hot-swap endpoint, restarting with {}={}",
+            model::MODEL_ENV,
+            model_id
+        );
+        model::emit_progress(&app, &model_id, model::SwitchPhase::Restarting, start, None);
+        launch_engine(&app, &proc_state, &log_state, &config, "model switch").and_then(|_| {
+            model::wait_until_loaded(&app, &config, &model_id, start).map_err(EngineError::from)
+        })
     };
-    Ok(lines[start..].join("\n"))
+
+    if let Err(e) = result {
+        // A hot swap that never finished may still land later, so only a
+        // failed restart goes back to the previous model
+        if !hot_swapped {
+            *app.state::<ActiveModel>().0.lock_or_recover() = previous;
+        }
+        model::emit_progress(
+            &app,
+            &model_id,
+            model::SwitchPhase::Failed,
+            start,
+            Some(e.to_string()),
+        );
+        return Err(e);
+    }
+    println!("[SOLAT] Engine switched to model {}", model_id);
+    model::emit_progress(&app, &model_id, model::SwitchPhase::Loaded, start, None);
+    Ok(model::ModelSwitch {
+        model_id,
+        hot_swapped,
+        elapsed_ms: telemetry::millis(start.elapsed()),
+    })
+}
+
+/// Check the engine venv and repair it if broken (`uv sync --reinstall`, then
+/// a fresh `.venv`). Progress is written to the boot log. The engine must be
+/// stopped first, and no launch can start until the repair is done.
+#[tauri::command]
+async fn repair_venv(
+    app: AppHandle,
+    proc_state: tauri::State<'_, EngineProcess>,
+    log_state: tauri::State<'_, EngineLogPath>,
+    lifecycle: tauri::State<'_, LifecycleLock>,
+) -> Result<venv::RepairReport, String> {
+    let _op = lifecycle.begin("venv repair").map_err(|e| e.to_string())?;
+    // An adopted engine may be running out of the same `.venv`
+    let (running, _) = engine_liveness(&app, &mut proc_state.0.lock_or_recover());
+    if running {
+        return Err("Stop the engine before repairing its venv".to_string());
+    }
+    let engine_dir = require_engine_dir().map_err(|e| e.to_string())?;
+    let log_path = log_state.0.lock_or_recover().clone();
+    let uv_path = resolve_uv_path();
+    venv::repair(&engine_dir, uv_path.as_deref(), &log_path)
+}
+
+/// Confirm the health URL is reachable through the app's HTTP client config
+/// and report the proxy settings it picked up from the environment.
+#[tauri::command]
+async fn verify_loopback_reachable(
+    app: AppHandle,
+    config_state: tauri::State<'_, EngineConfigState>,
+) -> Result<http::LoopbackReport, String> {
+    let config = config_state.current();
+    let addr = engine_addr(&app, &config)?;
+    let client = app.state::<http::HttpClient>().0.clone();
+    Ok(http::verify_loopback(&client, addr).await)
+}
+
+/// Expected time left in the current boot, from the median of recent
+/// successful boots; `None` before the first one.
+#[tauri::command]
+async fn estimate_startup_eta(
+    stats: tauri::State<'_, EngineStats>,
+) -> Result<Option<supervisor::StartupEta>, String> {
+    Ok(stats.estimate_startup_eta())
+}
+
+/// Let the watchdog restart a crash-looping engine again, after the cause
+/// was fixed. An engine that already crashed is relaunched on its next tick.
+#[tauri::command]
+async fn reset_restart_budget(budget: tauri::State<'_, RestartBudget>) -> Result<(), String> {
+    budget.reset();
+    println!("[SOLAT] Restart budget reset");
+    Ok(())
+}
+
+/// Most recent engine restarts (manual and watchdog), oldest first.
+#[tauri::command]
+async fn get_restart_history(
+    history: tauri::State<'_, RestartHistory>,
+) -> Result<Vec<RestartRecord>, String> {
+    Ok(history.snapshot())
+}
+
+/// Start streaming new engine log lines as batched `engine://log-lines` events.
+#[tauri::command]
+async fn tail_engine_log(
+    app: AppHandle,
+    stream_state: tauri::State<'_, LogStreamState>,
+) -> Result<bool, String> {
+    log_stream::start(app, &stream_state, true)
+}
+
+#[tauri::command]
+async fn stop_tail_engine_log(
+    stream_state: tauri::State<'_, LogStreamState>,
+) -> Result<bool, String> {
+    log_stream::stop(&stream_state)
+}
+
+/// Most lines `get_engine_log` returns, so a huge log isn't pulled into
+/// memory whole.
+const MAX_ENGINE_LOG_LINES: usize = 10_000;
+
+/// The last `lines` lines of the boot log (100 by default, at most
+/// `MAX_ENGINE_LOG_LINES`).
+#[tauri::command]
+async fn get_engine_log(
+    log_state: tauri::State<'_, EngineLogPath>,
+    lines: Option<usize>,
+) -> Result<logs::EngineLog, logs::LogError> {
+    let log_path = log_state.0.lock_or_recover().clone();
+    let lines = lines
+        .unwrap_or(logs::ENGINE_LOG_LINES)
+        .min(MAX_ENGINE_LOG_LINES);
+    logs::read_engine_log(&log_path, lines)
+}
+
+/// The previous boot's log (last 100 lines), as kept when the current boot
+/// started; a crash's output is there after the restart.
+#[tauri::command]
+async fn get_engine_log_previous(
+    log_state: tauri::State<'_, EngineLogPath>,
+) -> Result<logs::EngineLog, logs::LogError> {
+    let log_path = log_state.0.lock_or_recover().clone();
+    logs::read_previous_log(&log_path)
+}
+
+/// Bundle the boot logs and environment into a zip in Downloads for a bug
+/// report, returning its path so the UI can reveal it.
+#[tauri::command]
+async fn export_diagnostics(
+    log_state: tauri::State<'_, EngineLogPath>,
+    port_state: tauri::State<'_, EnginePort>,
+) -> Result<PathBuf, String> {
+    let log_path = log_state.0.lock_or_recover().clone();
+    let port = *port_state.0.lock_or_recover();
+    export::export_diagnostics(&log_path, port)
+}
+
+/// Most matches `search_engine_log` returns, whatever the caller asks for.
+const MAX_LOG_SEARCH_MATCHES: usize = 10_000;
+
+/// Boot log lines containing `query`, with their line numbers, so the UI
+/// can filter a long log without fetching it. Stops after `max_matches`.
+#[tauri::command]
+async fn search_engine_log(
+    log_state: tauri::State<'_, EngineLogPath>,
+    query: String,
+    case_insensitive: bool,
+    max_matches: usize,
+) -> Result<Vec<logs::LogMatch>, logs::LogError> {
+    let log_path = log_state.0.lock_or_recover().clone();
+    logs::search_log(
+        &log_path,
+        &query,
+        case_insensitive,
+        max_matches.min(MAX_LOG_SEARCH_MATCHES),
+    )
+}
+
+/// Truncate the boot log so the next lines start a clean one, returning the
+/// bytes cleared.
+#[tauri::command]
+async fn clear_engine_log(
+    log_state: tauri::State<'_, EngineLogPath>,
+) -> Result<u64, logs::LogError> {
+    let log_path = log_state.0.lock_or_recover().clone();
+    let cleared = logs::clear_log(&log_path)?;
+    println!(
+        "[SOLAT] Cleared {} bytes from {}",
+        cleared,
+        log_path.display()
+    );
+    Ok(cleared)
+}
+
+#[derive(serde::Serialize)]
+struct RotatedLog {
+    archived_path: String,
+    message: String,
+}
+
+/// Archive the current boot log and start a fresh empty one, e.g. before
+/// reproducing an issue so the export only holds the repro.
+#[tauri::command]
+async fn rotate_engine_log(
+    app: AppHandle,
+    proc_state: tauri::State<'_, EngineProcess>,
+    log_state: tauri::State<'_, EngineLogPath>,
+) -> Result<RotatedLog, logs::LogError> {
+    let log_path = log_state.0.lock_or_recover().clone();
+    let (running, _) = engine_liveness(&app, &mut proc_state.0.lock_or_recover());
+
+    let archived = logs::rotate_now(&log_path, running)?;
+    Ok(RotatedLog {
+        archived_path: archived.display().to_string(),
+        message: format!("Rotated to {}", archived.display()),
+    })
+}
+
+/// Upper bound on `get_structured_log` so a typo can't parse a huge log.
+const MAX_STRUCTURED_LOG_LINES: usize = 5000;
+
+/// The last `lines` log lines parsed into level/timestamp/message records,
+/// tracebacks grouped, for a log view with colours and collapsing.
+#[tauri::command]
+async fn get_structured_log(
+    log_state: tauri::State<'_, EngineLogPath>,
+    lines: usize,
+) -> Result<Vec<log_parse::LogEntry>, logs::LogError> {
+    let log_path = log_state.0.lock_or_recover().clone();
+    let tail = logs::read_tail_lines(&log_path, lines.clamp(1, MAX_STRUCTURED_LOG_LINES))?;
+    Ok(log_parse::parse_entries(tail.iter().map(String::as_str)))
+}
+
+/// Recent engine output from memory, available even when the boot log
+/// couldn't be written. Empty with `log_target: inherit`.
+#[tauri::command]
+async fn get_memory_log(memory_log: tauri::State<'_, MemoryLog>) -> Result<Vec<String>, String> {
+    Ok(memory_log.snapshot())
+}
+
+/// Record counts by level over the whole active log, for a diagnostics
+/// header like "3 errors, 12 warnings this session".
+#[tauri::command]
+async fn get_log_summary(
+    log_state: tauri::State<'_, EngineLogPath>,
+) -> Result<log_parse::LevelCounts, logs::LogError> {
+    let log_path = log_state.0.lock_or_recover().clone();
+    logs::summarize_log(&log_path)
+}
+
+/// Integrity check of the active log, so diagnostics can warn that it may
+/// be incomplete after a crash.
+#[tauri::command]
+async fn verify_log(log_state: tauri::State<'_, EngineLogPath>) -> Result<logs::LogHealth, String> {
+    let log_path = log_state.0.lock_or_recover().clone();
+    Ok(logs::verify_log(&log_path))
+}
+
+/// All log lines (with any traceback) tagged with a request/correlation ID,
+/// for matching a failed UI action to what the engine logged.
+#[tauri::command]
+async fn get_log_for_request(
+    log_state: tauri::State<'_, EngineLogPath>,
+    request_id: String,
+) -> Result<Vec<String>, logs::LogError> {
+    let log_path = log_state.0.lock_or_recover().clone();
+    logs::lines_for_request(&log_path, &request_id)
 }
 
 // ---------------------------------------------------------------------------
@@ -432,41 +2989,362 @@ fn read_log_tail_full(path: &PathBuf) -> Result<String, std::io::Error> {
 
 fn main() {
     // Compute log path early
-    let engine_dir = find_engine_dir().unwrap_or_else(|| PathBuf::from("."));
-    let log_path = engine_log_path(&engine_dir);
+    let stored_config = config::load_config(&config::config_path());
+    let env_overrides = config::EnvOverrides::from_env();
+    let mut engine_config = stored_config.clone();
+    env_overrides.apply(&mut engine_config);
+    if let Some(dir) = env_engine_dir().filter(|dir| !is_engine_dir(dir)) {
+        eprintln!(
+            "[SOLAT] WARNING: Ignoring SOLAT_ENGINE_DIR={}: it has no solat_engine/ folder",
+            dir.display()
+        );
+    }
+    let log_path = logs::engine_log_path(&resolve_data_dir(&engine_config));
+    if engine_config.log_retention_days > 0 {
+        if let Some(log_dir) = log_path.parent() {
+            logs::prune_old_logs(log_dir, engine_config.log_retention_days);
+        }
+    }
+
+    let http_client = http::build_http_client().expect("failed to build HTTP client");
+    // Until the first launch picks one, an auto port reports the default
+    let initial_port = match engine_config.port {
+        PortSetting::Fixed(port) => port,
+        PortSetting::Auto => config::DEFAULT_ENGINE_PORT,
+    };
 
     tauri::Builder::default()
+        // Must be registered first: a second launch hands off to the running
+        // instance and exits before its `setup` can spawn a rival engine.
+        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
+            println!("[SOLAT] Second instance launched, focusing existing window");
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.unminimize();
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }))
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .manage(EngineProcess(Mutex::new(None)))
+        .manage(LifecycleLock(Mutex::new(None)))
         .manage(EngineLogPath(Mutex::new(log_path.clone())))
+        .manage(EngineConfigState(Mutex::new(stored_config), env_overrides))
+        .manage(LaunchedConfig(Mutex::new(None)))
+        .manage(EnginePort(Mutex::new(initial_port)))
+        .manage(EngineUptime(Mutex::new(None)))
+        .manage(AdoptedEngine(Mutex::new(None)))
+        .manage(http::HttpClient(http_client))
+        .manage(RestartHistory::new())
+        .manage(RestartBudget::new())
+        .manage(EngineStats::new())
+        .manage(LogStreamState(Mutex::new(None)))
+        .manage(ProfileOutput(Mutex::new(None)))
+        .manage(ActiveModel(Mutex::new(None)))
+        .manage(DeepHealthState(Mutex::new(None)))
+        .manage(MemoryLog::new())
         .invoke_handler(tauri::generate_handler![
             start_engine,
+            start_engine_safe,
+            start_engine_profiled,
+            restart_engine,
+            restart_on_port,
             stop_engine,
             get_engine_status,
-            get_engine_log
+            get_engine_port,
+            set_engine_port,
+            set_log_level,
+            get_resolution_diagnostics,
+            refresh_uv_path,
+            set_engine_dir,
+            run_startup_diagnostics,
+            check_prerequisites,
+            get_engine_fd_count,
+            get_engine_log,
+            get_engine_log_previous,
+            clear_engine_log,
+            search_engine_log,
+            export_diagnostics,
+            get_log_for_request,
+            get_structured_log,
+            get_log_summary,
+            get_memory_log,
+            rotate_engine_log,
+            verify_log,
+            benchmark_engine,
+            measure_first_response,
+            check_api_compatibility,
+            cancel_engine_request,
+            verify_loopback_reachable,
+            set_engine_data_dir,
+            get_engine_config,
+            get_effective_config,
+            diff_config_from_default,
+            validate_engine_config,
+            set_engine_config,
+            get_restart_history,
+            reset_restart_budget,
+            estimate_startup_eta,
+            get_full_report,
+            pause_engine,
+            resume_engine,
+            switch_model,
+            tail_engine_log,
+            stop_tail_engine_log,
+            repair_venv
         ])
         .setup(move |app| {
-            // Non-blocking: spawn engine and return immediately.
-            // The splash screen handles health polling and shows progress.
-            println!("[SOLAT] Spawning engine (non-blocking)...");
-            ensure_port_free();
-            match spawn_engine(&log_path) {
-                Ok(child) => {
-                    let pid = child.id();
-                    println!("[SOLAT] Engine spawned (pid {})", pid);
-                    let state = app.state::<EngineProcess>();
-                    let mut guard = state.0.lock().unwrap();
-                    *guard = Some(child);
+            let handle = app.handle();
+            register_resource_engine_dir(handle);
+            // The early log path was derived before a bundled engine dir
+            // could be found
+            let log_path = logs::engine_log_path(&resolve_data_dir(&engine_config));
+            *app.state::<EngineLogPath>().0.lock_or_recover() = log_path.clone();
+            if let Some(port) = adopt_running_engine(handle, &engine_config) {
+                create_windows(app)?;
+                let _ = handle.emit(PORT_EVENT, port);
+                announce_ready(handle, port, Instant::now());
+            } else if std::env::var("SOLAT_BLOCKING_START").is_ok_and(|v| v == "1") {
+                start_engine_blocking(handle, &log_path, &engine_config)?;
+                create_windows(app)?;
+            } else {
+                create_windows(app)?;
+                // Non-blocking: spawn engine and return immediately. A
+                // background watch announces when it's ready (or failed).
+                if let Err(EngineError::EngineDirMissing { checked }) = require_engine_dir() {
+                    eprintln!(
+                        "[SOLAT] WARNING: No engine directory found; looked in: {}",
+                        checked.join(", ")
+                    );
+                    let payload = serde_json::json!({ "checked": checked });
+                    if let Err(e) = handle.emit(ENGINE_DIR_MISSING_EVENT, payload) {
+                        eprintln!("[SOLAT] WARNING: Failed to emit engine dir missing: {}", e);
+                    }
+                }
+                println!("[SOLAT] Spawning engine (non-blocking)...");
+                let port = select_port(handle, &engine_config)?;
+                if let Err(e) = ensure_port_free(&handle.state::<http::HttpClient>().0, port) {
+                    // The spawn still goes ahead; a bind failure shows in the boot log
+                    eprintln!("[SOLAT] WARNING: {}", e);
+                }
+                let start = Instant::now();
+                telemetry::emit(
+                    handle,
+                    &engine_config,
+                    TelemetryEvent::SpawnAttempt {
+                        safe_mode: engine_config.safe_mode,
+                    },
+                );
+                let profile_output = prepare_profile_output(handle, &engine_config);
+                match spawn_engine(
+                    &log_path,
+                    &engine_config,
+                    port,
+                    profile_output.as_deref(),
+                    None,
+                    &handle.state::<MemoryLog>(),
+                ) {
+                    Ok(child) => {
+                        let pid = child.id();
+                        println!("[SOLAT] Engine spawned (pid {})", pid);
+                        telemetry::emit(
+                            handle,
+                            &engine_config,
+                            TelemetryEvent::SpawnSuccess {
+                                pid,
+                                duration_ms: telemetry::millis(start.elapsed()),
+                                health_confirmed: false,
+                            },
+                        );
+                        let state = app.state::<EngineProcess>();
+                        let mut guard = state.0.lock_or_recover();
+                        *guard = Some(child);
+                        record_engine_start(handle, &engine_config, pid);
+                        spawn_boot_watch(
+                            handle.clone(),
+                            engine_config.clone(),
+                            log_path.clone(),
+                            start,
+                        );
+                    }
+                    Err(e) => {
+                        let e = e.to_string();
+                        eprintln!("[SOLAT] Failed to spawn engine: {}", e);
+                        announce_boot_failed(handle, &e, "");
+                        telemetry::emit(
+                            handle,
+                            &engine_config,
+                            TelemetryEvent::SpawnFailure {
+                                reason: e,
+                                duration_ms: telemetry::millis(start.elapsed()),
+                            },
+                        );
+                        // Don't panic — splash screen will show error and retry button
+                    }
                 }
-                Err(e) => {
-                    eprintln!("[SOLAT] Failed to spawn engine: {}", e);
-                    // Don't panic — splash screen will show error and retry button
+            }
+            // Stream the new boot log from its first line
+            if let Err(e) =
+                log_stream::start(handle.clone(), &handle.state::<LogStreamState>(), false)
+            {
+                eprintln!("[SOLAT] WARNING: Failed to start log streaming: {}", e);
+            }
+            supervisor::spawn_watchdog(handle.clone());
+            supervisor::spawn_log_guard(handle.clone());
+            canary::spawn_deep_health(handle.clone());
+            monitor::spawn_health_monitor(handle.clone());
+            schedule::spawn_restart_scheduler(handle.clone());
+
+            // Ctrl-C in `cargo tauri dev` (or a SIGTERM) would otherwise exit
+            // without reaping the engine, leaving it holding the port. An
+            // adopted engine isn't ours to reap and is left for next launch.
+            let signal_handle = handle.clone();
+            if let Err(e) = ctrlc::set_handler(move || {
+                println!("[SOLAT] Received termination signal, stopping engine...");
+                if let Err(e) = terminate_engine(&signal_handle, STOP_GRACE, false) {
+                    eprintln!("[SOLAT] Failed to stop engine on shutdown: {}", e);
                 }
+                std::process::exit(130);
+            }) {
+                eprintln!("[SOLAT] WARNING: Failed to install signal handler: {}", e);
             }
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // Closing the last window (or `app.exit`) ends the process without
+            // running destructors, so the engine has to be stopped here or it
+            // outlives us holding the port. `Exit` covers anything that skips
+            // `ExitRequested`; by then the child is normally gone already.
+            // An adopted engine stays up for the next launch to adopt.
+            if let RunEvent::ExitRequested { .. } | RunEvent::Exit = event {
+                match terminate_engine(app, STOP_GRACE, false) {
+                    Ok(true) => println!("[SOLAT] Engine stopped on exit"),
+                    Ok(false) => {}
+                    Err(e) => eprintln!("[SOLAT] Failed to stop engine on exit: {}", e),
+                }
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[cfg(unix)]
+    fn idle_child() -> Result<(Child, ()), EngineError> {
+        let child = StdCommand::new("sleep")
+            .arg("30")
+            .spawn()
+            .map_err(|e| e.to_string())?;
+        Ok((child, ()))
+    }
+
+    /// `stop_old` for the tests: kill the replaced child, reporting its pid.
+    #[cfg(unix)]
+    fn kill_old(old: Option<Child>) -> Result<Option<u32>, EngineError> {
+        Ok(old.map(|mut child| {
+            let _ = child.kill();
+            let _ = child.wait();
+            child.id()
+        }))
+    }
+
+    /// Two starts racing through `start_managed`: the one that wins the
+    /// lifecycle lock spawns, the other is refused with `Busy`, and a
+    /// single child ends up managed. A later start then replaces it.
+    #[cfg(unix)]
+    #[test]
+    fn concurrent_starts_manage_one_child() {
+        let lifecycle = LifecycleLock(Mutex::new(None));
+        let proc_state = EngineProcess(Mutex::new(None));
+        let finished = AtomicUsize::new(0);
+        let spawn = || {
+            // Hold the lock (as a health wait would) until the other start
+            // has finished, so the two certainly overlap
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while finished.load(Ordering::SeqCst) == 0 && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            idle_child()
+        };
+        let start = || {
+            let result =
+                start_managed(&lifecycle, &proc_state, "start", kill_old, spawn).map(|m| m.pid);
+            finished.fetch_add(1, Ordering::SeqCst);
+            result
+        };
+
+        let results = std::thread::scope(|scope| {
+            let first = scope.spawn(start);
+            let second = scope.spawn(start);
+            [first.join().unwrap(), second.join().unwrap()]
+        });
+
+        let busy = results
+            .iter()
+            .filter(|result| matches!(result, Err(EngineError::Busy { operation }) if operation == "start"))
+            .count();
+        assert_eq!(busy, 1, "{:?}", results);
+        let started: Vec<u32> = results
+            .iter()
+            .filter_map(|r| r.as_ref().ok().copied())
+            .collect();
+        assert_eq!(started.len(), 1, "{:?}", results);
+        let managed = proc_state.0.lock_or_recover().as_ref().map(Child::id);
+        assert_eq!(managed, Some(started[0]));
+        assert!(!lifecycle.is_busy());
+
+        let restarted = start_managed(&lifecycle, &proc_state, "restart", kill_old, idle_child)
+            .expect("restart failed");
+        assert_eq!(restarted.stopped, Some(started[0]));
+        let managed = proc_state.0.lock_or_recover().as_ref().map(Child::id);
+        assert_eq!(managed, Some(restarted.pid));
+        drop(restarted);
+        let _ = kill_old(proc_state.0.lock_or_recover().take());
+    }
+
+    #[test]
+    fn port_is_occupied_handles_edge_ports() {
+        // Whether anything listens there varies; only the probe must not panic
+        let _ = port_is_occupied(1);
+        let _ = port_is_occupied(65535);
+
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert!(port_is_occupied(port));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parse_ss_pids_reads_matching_rows() {
+        let output = "\
+State  Recv-Q Send-Q Local Address:Port  Peer Address:Port Process
+LISTEN 0      2048       127.0.0.1:8765       0.0.0.0:*     users:((\"python3\",pid=4242,fd=6))
+LISTEN 0      2048           [::1]:8765          [::]:*     users:((\"uvicorn\",pid=4300,fd=7),(\"uvicorn\",pid=4301,fd=7))
+LISTEN 0      128        127.0.0.1:18765      0.0.0.0:*     users:((\"other\",pid=99,fd=3))
+LISTEN 0      4096   127.0.0.53%lo:53         0.0.0.0:*     users:((\"systemd-resolve\",pid=612,fd=14))
+LISTEN 0      511          0.0.0.0:8080       0.0.0.0:*
+";
+        assert_eq!(parse_ss_pids(output, 8765), vec![4242, 4300, 4301]);
+        assert_eq!(parse_ss_pids(output, 53), vec![612]);
+        assert_eq!(parse_ss_pids(output, 8080), Vec::<u32>::new());
+        assert_eq!(parse_ss_pids(output, 9999), Vec::<u32>::new());
+        assert_eq!(parse_ss_pids("", 8765), Vec::<u32>::new());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parse_pid_list_reads_lsof_and_fuser_output() {
+        // lsof -t: one pid per line
+        assert_eq!(parse_pid_list("4242\n4300\n"), vec![4242, 4300]);
+        // fuser: pids on one line (the "8765/tcp:" label goes to stderr)
+        assert_eq!(parse_pid_list("  4242  4300"), vec![4242, 4300]);
+        assert_eq!(parse_pid_list(" 4242e 4300c\n"), vec![4242, 4300]);
+        assert_eq!(parse_pid_list(""), Vec::<u32>::new());
+        assert_eq!(parse_pid_list("8765/tcp:"), Vec::<u32>::new());
+    }
 }
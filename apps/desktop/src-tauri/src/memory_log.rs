@@ -0,0 +1,92 @@
+//! Recent engine output kept in memory, so the UI can show it even when the
+//! boot log can't be written (read-only data dir, full disk).
+//!
+//! The engine's stdout and stderr are pipes, each drained by its own pump
+//! thread that copies output to the log file (and console, for
+//! `log_target: both`) and the last `MEMORY_LOG_LINES` lines into the ring
+//! buffer. A pipe is always being read, so the engine can never block on a
+//! full pipe buffer; a failed file write drops the file, never the pump.
+//! The pumps die with the launcher, though, so an engine that outlives it
+//! has nowhere to write and isn't adopted by the next launch.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::sync::{Arc, Mutex};
+
+use crate::logs;
+use crate::sync::LockOrRecover;
+
+const MEMORY_LOG_LINES: usize = 2000;
+
+/// Handle to the shared ring buffer; clones share it.
+#[derive(Clone)]
+pub struct MemoryLog(Arc<Mutex<VecDeque<String>>>);
+
+impl MemoryLog {
+    pub fn new() -> Self {
+        Self(Arc::new(Mutex::new(VecDeque::with_capacity(
+            MEMORY_LOG_LINES,
+        ))))
+    }
+
+    pub fn push(&self, line: String) {
+        let mut lines = self.0.lock_or_recover();
+        if lines.len() == MEMORY_LOG_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    pub fn snapshot(&self) -> Vec<String> {
+        self.0.lock_or_recover().iter().cloned().collect()
+    }
+}
+
+/// Console stream a pump echoes to, besides the log file.
+#[derive(Debug, Clone, Copy)]
+pub enum Echo {
+    Stdout,
+    Stderr,
+}
+
+/// Drain `source` on a dedicated thread until the engine closes it.
+pub fn spawn_pump(
+    source: impl Read + Send + 'static,
+    mut log: Option<File>,
+    echo: Option<Echo>,
+    memory: MemoryLog,
+) {
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(source);
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            match reader.read_until(b'\n', &mut line) {
+                Ok(0) => return,
+                Ok(_) => {}
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(_) => return,
+            }
+            if let Some(file) = log.as_mut() {
+                if let Err(e) = file.write_all(&line) {
+                    eprintln!(
+                        "[SOLAT] WARNING: Failed to write engine log ({}); output is only kept in memory now",
+                        e
+                    );
+                    log = None;
+                }
+            }
+            let _ = match echo {
+                Some(Echo::Stdout) => io::stdout().write_all(&line),
+                Some(Echo::Stderr) => io::stderr().write_all(&line),
+                None => Ok(()),
+            };
+            memory.push(
+                logs::sanitize_log_bytes(&line)
+                    .trim_end_matches(['\r', '\n'])
+                    .to_string(),
+            );
+        }
+    });
+}
@@ -0,0 +1,121 @@
+//! Switching the model the engine serves.
+//!
+//! An engine with a `POST /admin/model` endpoint swaps models in place; the
+//! launcher then polls `/health` until the engine is healthy again and, if
+//! the body names its model (`model` or `active_model`), until it names the
+//! new one. An engine without the endpoint is restarted with `SOLAT_MODEL`
+//! set instead. Either way the chosen model is kept in `ActiveModel` and
+//! passed as `SOLAT_MODEL` to every later launch, so a crash restart doesn't
+//! fall back to the engine's default.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Emitter};
+
+use crate::config::EngineConfig;
+
+pub const MODEL_ENV: &str = "SOLAT_MODEL";
+const MODEL_SWITCH_EVENT: &str = "engine://model-switch";
+/// Loading a model can mean reading gigabytes from disk.
+pub const MODEL_SWITCH_TIMEOUT_SECS: u64 = 120;
+const POLL_INTERVAL_MS: u64 = 500;
+
+/// Model chosen through `switch_model`, if any.
+pub struct ActiveModel(pub Mutex<Option<String>>);
+
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SwitchPhase {
+    /// The switch request was sent to the engine.
+    Requested,
+    /// The engine has not reported the new model yet.
+    Loading,
+    /// No hot-swap endpoint: restarting with `SOLAT_MODEL`.
+    Restarting,
+    Loaded,
+    Failed,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct ModelSwitchEvent {
+    model_id: String,
+    phase: SwitchPhase,
+    elapsed_ms: u64,
+    detail: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ModelSwitch {
+    pub model_id: String,
+    /// False when the engine had to be restarted to change model.
+    pub hot_swapped: bool,
+    pub elapsed_ms: u64,
+}
+
+pub fn emit_progress(
+    app: &AppHandle,
+    model_id: &str,
+    phase: SwitchPhase,
+    start: Instant,
+    detail: Option<String>,
+) {
+    let event = ModelSwitchEvent {
+        model_id: model_id.to_string(),
+        phase,
+        elapsed_ms: crate::telemetry::millis(start.elapsed()),
+        detail,
+    };
+    if let Err(e) = app.emit(MODEL_SWITCH_EVENT, &event) {
+        eprintln!("[SOLAT] WARNING: Failed to emit model-switch: {}", e);
+    }
+}
+
+/// The model a `/health` body names, if it names one.
+pub fn reported_model(body: &str) -> Option<String> {
+    let json: serde_json::Value = serde_json::from_str(body).ok()?;
+    ["model", "active_model"]
+        .iter()
+        .find_map(|key| json.get(key).and_then(|v| v.as_str()))
+        .map(str::to_string)
+}
+
+/// Poll `/health` until the engine is healthy and, if it reports a model,
+/// reports `model_id`. Emits a `loading` event whenever what it reports
+/// changes.
+pub fn wait_until_loaded(
+    app: &AppHandle,
+    config: &EngineConfig,
+    model_id: &str,
+    start: Instant,
+) -> Result<(), String> {
+    let deadline = Duration::from_secs(MODEL_SWITCH_TIMEOUT_SECS);
+    let mut last_report = None;
+    while start.elapsed() < deadline {
+        let (healthy, body, error) = crate::probe_health(app, config);
+        let reported = body.as_deref().and_then(reported_model);
+        if healthy && reported.as_deref().is_none_or(|model| model == model_id) {
+            return Ok(());
+        }
+        let report = match (&reported, &error) {
+            (Some(model), _) => format!("engine reports model {}", model),
+            (None, Some(error)) => error.clone(),
+            (None, None) => "engine not healthy yet".to_string(),
+        };
+        if last_report.as_ref() != Some(&report) {
+            emit_progress(
+                app,
+                model_id,
+                SwitchPhase::Loading,
+                start,
+                Some(report.clone()),
+            );
+            last_report = Some(report);
+        }
+        std::thread::sleep(Duration::from_millis(POLL_INTERVAL_MS));
+    }
+    Err(format!(
+        "Engine did not report model {} within {}s",
+        model_id, MODEL_SWITCH_TIMEOUT_SECS
+    ))
+}
@@ -0,0 +1,112 @@
+//! Health monitoring after boot: catches an engine whose process is alive
+//! but whose server has wedged, which the watchdog (watching for exits)
+//! can't see.
+//!
+//! Every `INTERVAL_SECS` the running engine's `/health` is probed, but only
+//! once that process has been healthy at least once, so a boot in progress
+//! never counts as a failure. Transitions are announced as
+//! `engine://health-changed`; `UNRESPONSIVE_AFTER` failures in a row also
+//! announce `engine://unresponsive` (once per run of failures) so the UI can
+//! offer a restart.
+
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::sync::LockOrRecover;
+use crate::{EngineConfigState, EngineProcess};
+
+const HEALTH_CHANGED_EVENT: &str = "engine://health-changed";
+const UNRESPONSIVE_EVENT: &str = "engine://unresponsive";
+const INTERVAL_SECS: u64 = 10;
+const UNRESPONSIVE_AFTER: u32 = 3;
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct HealthChangedEvent {
+    healthy: bool,
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct UnresponsiveEvent {
+    consecutive_failures: u32,
+    error: Option<String>,
+}
+
+/// What the monitor knows about the engine process it's watching.
+struct Watched {
+    pid: u32,
+    /// Latest health seen; `None` until the process first passes.
+    healthy: Option<bool>,
+    failures: u32,
+}
+
+pub fn spawn_health_monitor(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut watched: Option<Watched> = None;
+        loop {
+            std::thread::sleep(Duration::from_secs(INTERVAL_SECS));
+
+            let liveness =
+                crate::child_liveness(&mut app.state::<EngineProcess>().0.lock_or_recover());
+            let (true, Some(pid)) = liveness else {
+                watched = None;
+                continue;
+            };
+            let config = app.state::<EngineConfigState>().current();
+            // A new process starts unobserved: its boot is the boot watch's
+            let current = match &mut watched {
+                Some(current) if current.pid == pid => current,
+                _ => watched.insert(Watched {
+                    pid,
+                    healthy: None,
+                    failures: 0,
+                }),
+            };
+
+            let (healthy, _, error) = crate::probe_health(&app, &config);
+            if healthy {
+                current.failures = 0;
+            } else if current.healthy.is_some() {
+                current.failures += 1;
+            } else {
+                continue;
+            }
+
+            if current.healthy != Some(healthy) {
+                if current.healthy.is_some() {
+                    if healthy {
+                        println!("[SOLAT] Engine healthy again");
+                    } else {
+                        eprintln!(
+                            "[SOLAT] WARNING: Engine stopped passing /health: {}",
+                            error.as_deref().unwrap_or("unknown error")
+                        );
+                    }
+                    let event = HealthChangedEvent {
+                        healthy,
+                        error: error.clone(),
+                    };
+                    if let Err(e) = app.emit(HEALTH_CHANGED_EVENT, &event) {
+                        eprintln!("[SOLAT] WARNING: Failed to emit health change: {}", e);
+                    }
+                }
+                current.healthy = Some(healthy);
+            }
+
+            if current.failures == UNRESPONSIVE_AFTER {
+                eprintln!(
+                    "[SOLAT] WARNING: Engine pid {} unresponsive: {} health checks failed in a row",
+                    pid, current.failures
+                );
+                let event = UnresponsiveEvent {
+                    consecutive_failures: current.failures,
+                    error,
+                };
+                if let Err(e) = app.emit(UNRESPONSIVE_EVENT, &event) {
+                    eprintln!("[SOLAT] WARNING: Failed to emit unresponsive: {}", e);
+                }
+            }
+        }
+    });
+}
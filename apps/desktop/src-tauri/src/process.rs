@@ -0,0 +1,414 @@
+//! OS-specific controls applied to the engine child process.
+
+use std::ffi::OsStr;
+use std::process::{Child, Command, ExitStatus};
+use std::time::{Duration, Instant};
+
+use crate::config::ProcessPriority;
+
+/// Env keys / flag names containing any of these have their values redacted
+/// when a command line is written to the log.
+const SENSITIVE_MARKERS: [&str; 5] = ["KEY", "TOKEN", "SECRET", "PASSWORD", "CREDENTIAL"];
+
+/// `program prefix_args... <inner program> <inner args>...`. Only the program
+/// and args of `inner` are carried over, so call this before setting env or
+/// cwd.
+pub fn prefix_command<I, S>(program: impl AsRef<OsStr>, prefix_args: I, inner: &Command) -> Command
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let mut command = Command::new(program);
+    command
+        .args(prefix_args)
+        .arg(inner.get_program())
+        .args(inner.get_args());
+    command
+}
+
+#[cfg(unix)]
+fn nice_value(priority: ProcessPriority) -> i32 {
+    match priority {
+        ProcessPriority::Low => 10,
+        ProcessPriority::Normal => 0,
+        ProcessPriority::High => -5,
+    }
+}
+
+/// Arrange for the child to start at `priority`.
+///
+/// On Unix the niceness is set in the forked child before exec rather than
+/// after spawn, so that the Python process uv forks immediately inherits it.
+#[cfg(unix)]
+pub fn apply_priority(command: &mut Command, priority: ProcessPriority) {
+    use std::os::unix::process::CommandExt;
+
+    if priority == ProcessPriority::Normal {
+        return;
+    }
+    let nice = nice_value(priority);
+    // SAFETY: setpriority is async-signal-safe and touches no parent state.
+    // Failure (e.g. raising priority without privileges) is ignored here and
+    // reported by `verify_priority` once the child is running.
+    unsafe {
+        command.pre_exec(move || {
+            libc::setpriority(libc::PRIO_PROCESS, 0, nice);
+            Ok(())
+        });
+    }
+}
+
+#[cfg(windows)]
+pub fn apply_priority(command: &mut Command, priority: ProcessPriority) {
+    use std::os::windows::process::CommandExt;
+
+    // ABOVE_NORMAL rather than HIGH mirrors the modest nice -5 used on Unix.
+    const BELOW_NORMAL_PRIORITY_CLASS: u32 = 0x0000_4000;
+    const ABOVE_NORMAL_PRIORITY_CLASS: u32 = 0x0000_8000;
+
+    match priority {
+        ProcessPriority::Low => {
+            command.creation_flags(BELOW_NORMAL_PRIORITY_CLASS);
+        }
+        ProcessPriority::Normal => {}
+        ProcessPriority::High => {
+            command.creation_flags(ABOVE_NORMAL_PRIORITY_CLASS);
+        }
+    }
+}
+
+/// Warn if the spawned child didn't end up at the requested priority.
+#[cfg(unix)]
+pub fn verify_priority(child: &Child, priority: ProcessPriority) {
+    if priority == ProcessPriority::Normal {
+        return;
+    }
+    let expected = nice_value(priority);
+    // SAFETY: plain syscall on a pid we own; an unexpected value (including
+    // the -1 error return) is simply reported as a mismatch.
+    let actual = unsafe { libc::getpriority(libc::PRIO_PROCESS, child.id() as libc::id_t) };
+    if actual != expected {
+        eprintln!(
+            "[SOLAT] WARNING: Requested {:?} priority (nice {}) but engine runs at nice {}",
+            priority, expected, actual
+        );
+    } else {
+        println!(
+            "[SOLAT] Engine running at {:?} priority (nice {})",
+            priority, actual
+        );
+    }
+}
+
+#[cfg(windows)]
+pub fn verify_priority(_child: &Child, _priority: ProcessPriority) {}
+
+/// Cap the child's address space at `limit_mb` MiB (0 = no cap). Set before
+/// exec like the priority, so the Python process uv forks inherits it.
+#[cfg(target_os = "linux")]
+pub fn apply_memory_limit(command: &mut Command, limit_mb: u64) {
+    use std::os::unix::process::CommandExt;
+
+    if limit_mb == 0 {
+        return;
+    }
+    let bytes = limit_mb.saturating_mul(1024 * 1024) as libc::rlim_t;
+    println!("[SOLAT] Engine memory limit: {} MB", limit_mb);
+    // SAFETY: setrlimit is async-signal-safe and only affects the child.
+    unsafe {
+        command.pre_exec(move || {
+            let limit = libc::rlimit {
+                rlim_cur: bytes,
+                rlim_max: bytes,
+            };
+            if libc::setrlimit(libc::RLIMIT_AS, &limit) != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn apply_memory_limit(_command: &mut Command, limit_mb: u64) {
+    if limit_mb != 0 {
+        eprintln!(
+            "[SOLAT] WARNING: memory_limit_mb ({} MB) is only supported on Linux; engine runs without a limit",
+            limit_mb
+        );
+    }
+}
+
+/// What allocation failure under an address-space limit leaves in the log.
+const OUT_OF_MEMORY_MARKERS: [&str; 4] = [
+    "MemoryError",
+    "Cannot allocate memory",
+    "std::bad_alloc",
+    "out of memory",
+];
+
+/// A readable account of why the engine exited. With `memory_limit_mb` set,
+/// an exit that looks like failed allocation (an out-of-memory message in
+/// `log_tail`, or the abort/segfault of an unchecked malloc) is reported as
+/// "hit memory limit".
+pub fn explain_exit(status: ExitStatus, memory_limit_mb: u64, log_tail: &str) -> String {
+    #[cfg(unix)]
+    let crashed = {
+        use std::os::unix::process::ExitStatusExt;
+        matches!(
+            status.signal(),
+            Some(libc::SIGABRT | libc::SIGSEGV | libc::SIGBUS | libc::SIGKILL)
+        )
+    };
+    #[cfg(not(unix))]
+    let crashed = false;
+
+    let out_of_memory = OUT_OF_MEMORY_MARKERS
+        .iter()
+        .any(|marker| log_tail.contains(marker));
+    if memory_limit_mb != 0 && (out_of_memory || crashed) {
+        format!("hit memory limit ({} MB): {}", memory_limit_mb, status)
+    } else {
+        status.to_string()
+    }
+}
+
+/// Ask the engine to exit (SIGTERM on Unix, a plain `taskkill` on Windows),
+/// so uvicorn can finish requests and close its sockets, and kill it if it's
+/// still running after `grace`. Reaps the child either way.
+pub fn stop_engine_gracefully(child: &mut Child, grace: Duration) -> std::io::Result<()> {
+    if let Ok(Some(_)) = child.try_wait() {
+        return Ok(());
+    }
+    if !grace.is_zero() {
+        request_exit(child.id());
+        let deadline = Instant::now() + grace;
+        while Instant::now() < deadline {
+            if let Ok(Some(_)) = child.try_wait() {
+                return Ok(());
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        eprintln!(
+            "[SOLAT] WARNING: Engine did not exit within {:.1}s, killing it",
+            grace.as_secs_f64()
+        );
+    }
+    let killed = child.kill();
+    let _ = child.wait();
+    killed
+}
+
+#[cfg(unix)]
+fn request_exit(pid: u32) {
+    // SAFETY: kill(2) takes any pid; callers only pass the engine's.
+    unsafe {
+        libc::kill(pid as libc::pid_t, libc::SIGTERM);
+    }
+}
+
+/// Without `/F`, taskkill asks the process to close; console processes may
+/// refuse, which the grace period then covers.
+#[cfg(windows)]
+fn request_exit(pid: u32) {
+    let _ = Command::new("taskkill")
+        .args(["/PID", &pid.to_string()])
+        .output();
+}
+
+/// Whether a process with `pid` exists (one we may not signal counts).
+#[cfg(unix)]
+pub fn pid_alive(pid: u32) -> bool {
+    // SAFETY: signal 0 only checks the pid; nothing is delivered.
+    let result = unsafe { libc::kill(pid as libc::pid_t, 0) };
+    result == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(windows)]
+pub fn pid_alive(pid: u32) -> bool {
+    Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/NH", "/FO", "CSV"])
+        .output()
+        .is_ok_and(|output| {
+            String::from_utf8_lossy(&output.stdout).contains(&format!("\"{}\"", pid))
+        })
+}
+
+/// `stop_engine_gracefully` for an engine that isn't our child (adopted
+/// from an earlier session): ask it to exit and wait up to `grace`.
+/// Returns whether it's gone; the caller force-kills it otherwise.
+pub fn stop_pid_gracefully(pid: u32, grace: Duration) -> bool {
+    if !pid_alive(pid) {
+        return true;
+    }
+    request_exit(pid);
+    let deadline = Instant::now() + grace;
+    while Instant::now() < deadline {
+        if !pid_alive(pid) {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    !pid_alive(pid)
+}
+
+/// Wait for `child`, killing it once `timeout` seconds have passed.
+pub fn wait_bounded(child: &mut Child, timeout: u64) -> Result<ExitStatus, String> {
+    let deadline = Instant::now() + Duration::from_secs(timeout);
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return Ok(status),
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("timed out after {}s", timeout));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(100)),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+}
+
+fn is_sensitive(name: &str) -> bool {
+    let upper = name.to_uppercase();
+    SENSITIVE_MARKERS
+        .iter()
+        .any(|marker| upper.contains(marker))
+}
+
+fn quote(value: &OsStr) -> String {
+    let value = value.to_string_lossy();
+    if value.is_empty() || value.contains(char::is_whitespace) {
+        format!("'{}'", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// One-line description of `command` (program, args, cwd and the env keys
+/// we override) for reproducing a launch by hand. Values of secret-looking
+/// env vars and `--flag value` / `--flag=value` args are redacted.
+pub fn describe_command(command: &Command) -> String {
+    let mut parts = vec![quote(command.get_program())];
+    let mut redact_next = false;
+    for arg in command.get_args() {
+        let text = arg.to_string_lossy();
+        if redact_next {
+            parts.push("***".to_string());
+            redact_next = false;
+        } else if let Some((flag, _)) = text.split_once('=').filter(|(f, _)| f.starts_with("--")) {
+            if is_sensitive(flag) {
+                parts.push(format!("{}=***", flag));
+            } else {
+                parts.push(quote(arg));
+            }
+        } else {
+            redact_next = text.starts_with("--") && is_sensitive(&text);
+            parts.push(quote(arg));
+        }
+    }
+
+    let cwd = command
+        .get_current_dir()
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_else(|| "(inherited)".to_string());
+
+    let env: Vec<String> = command
+        .get_envs()
+        .map(|(key, value)| {
+            let key = key.to_string_lossy();
+            match value {
+                _ if is_sensitive(&key) => format!("{}=***", key),
+                Some(value) => format!("{}={}", key, quote(value)),
+                None => format!("{} (unset)", key),
+            }
+        })
+        .collect();
+
+    format!(
+        "{} (cwd: {}; env: {})",
+        parts.join(" "),
+        cwd,
+        if env.is_empty() {
+            "no overrides".to_string()
+        } else {
+            env.join(", ")
+        }
+    )
+}
+
+/// `pid` and all its descendants. `uv run` keeps running as the parent of
+/// the Python process, so per-process figures for the engine have to cover
+/// the whole tree.
+#[cfg(unix)]
+fn process_tree(pid: u32) -> Vec<u32> {
+    let mut pids = vec![pid];
+    let mut next = 0;
+    while next < pids.len() {
+        let parent = pids[next];
+        next += 1;
+        let children = std::fs::read_to_string(format!("/proc/{0}/task/{0}/children", parent))
+            .or_else(|_| {
+                Command::new("pgrep")
+                    .args(["-P", &parent.to_string()])
+                    .output()
+                    .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+            })
+            .unwrap_or_default();
+        pids.extend(
+            children
+                .split_whitespace()
+                .filter_map(|pid| pid.parse::<u32>().ok()),
+        );
+    }
+    pids
+}
+
+/// Open file descriptors across the engine's process tree, or `None` if the
+/// root process can't be inspected (e.g. it has exited).
+#[cfg(unix)]
+pub fn open_fd_count(pid: u32) -> Option<usize> {
+    let mut total = 0;
+    for (i, pid) in process_tree(pid).into_iter().enumerate() {
+        let count = match std::fs::read_dir(format!("/proc/{}/fd", pid)) {
+            Ok(entries) => Some(entries.count()),
+            // No procfs (macOS): count lsof's rows, minus its header
+            Err(_) => Command::new("lsof")
+                .args(["-n", "-P", "-p", &pid.to_string()])
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| {
+                    output
+                        .stdout
+                        .split(|b| *b == b'\n')
+                        .count()
+                        .saturating_sub(2)
+                }),
+        };
+        match count {
+            Some(count) => total += count,
+            // Only the root is required; a descendant may exit mid-walk
+            None if i == 0 => return None,
+            None => {}
+        }
+    }
+    Some(total)
+}
+
+/// Open handles of the engine process and its direct children.
+#[cfg(windows)]
+pub fn open_fd_count(pid: u32) -> Option<usize> {
+    let script = format!(
+        "$p = @(Get-Process -Id {0} -ErrorAction Stop) + @(Get-CimInstance Win32_Process \
+         -Filter 'ParentProcessId={0}' | ForEach-Object {{ Get-Process -Id $_.ProcessId \
+         -ErrorAction SilentlyContinue }}); ($p | Measure-Object HandleCount -Sum).Sum",
+        pid
+    );
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
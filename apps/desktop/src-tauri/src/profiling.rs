@@ -0,0 +1,138 @@
+//! Running the engine under `py-spy record` for diagnosing slow endpoints.
+//!
+//! With `profile` set in the config the launch command is wrapped in
+//! py-spy, which writes a speedscope profile to `<data dir>/profiles/` once
+//! the engine stops. py-spy only flushes on SIGINT, so a profiled engine is
+//! interrupted (and given time to write) before it is killed; on Windows,
+//! where there is no SIGINT, the profile is only written if the engine
+//! exits on its own. If py-spy isn't installed the engine is started
+//! normally with `SOLAT_PROFILE=1` / `SOLAT_PROFILE_OUTPUT` for the engine
+//! to honour if it can.
+
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Emitter};
+
+use crate::process;
+
+const PROFILE_READY_EVENT: &str = "engine://profile-ready";
+/// How long py-spy gets to write the profile after SIGINT.
+const PROFILE_FLUSH_SECS: u64 = 10;
+
+/// Output file of the profiled engine currently running, if any.
+pub struct ProfileOutput(pub Mutex<Option<PathBuf>>);
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct ProfileReadyEvent {
+    path: String,
+}
+
+/// A fresh `engine-profile-<timestamp>.json` path under `data_dir/profiles`.
+pub fn output_path(data_dir: &Path) -> PathBuf {
+    let profile_dir = data_dir.join("profiles");
+    let _ = std::fs::create_dir_all(&profile_dir);
+    profile_dir.join(format!(
+        "engine-profile-{}.json",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ))
+}
+
+/// py-spy from the engine venv, else from `PATH`.
+pub fn find_py_spy(engine_dir: &Path) -> Option<PathBuf> {
+    let binary = if cfg!(windows) {
+        "py-spy.exe"
+    } else {
+        "py-spy"
+    };
+    let venv_bin = if cfg!(windows) { "Scripts" } else { "bin" };
+
+    let venv = engine_dir.join(".venv").join(venv_bin).join(binary);
+    if venv.exists() {
+        return Some(venv);
+    }
+    std::env::var_os("PATH").and_then(|path| {
+        std::env::split_paths(&path)
+            .map(|dir| dir.join(binary))
+            .find(|candidate| candidate.exists())
+    })
+}
+
+/// `py-spy record ... -- <program> <args>` for `inner`. Only the program and
+/// args are carried over, so call this before setting env or cwd.
+pub fn wrap_command(py_spy: &Path, output: &Path, inner: &Command) -> Command {
+    let record_args = [
+        OsStr::new("record"),
+        OsStr::new("-o"),
+        output.as_os_str(),
+        OsStr::new("--format"),
+        OsStr::new("speedscope"),
+        OsStr::new("--subprocesses"),
+        OsStr::new("--"),
+    ];
+    let mut command = process::prefix_command(py_spy, record_args, inner);
+    own_process_group(&mut command);
+    command
+}
+
+/// Own process group, so the interrupt reaches py-spy and the engine under
+/// it together, as a terminal Ctrl-C would.
+#[cfg(unix)]
+fn own_process_group(command: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    command.process_group(0);
+}
+
+#[cfg(windows)]
+fn own_process_group(_command: &mut Command) {}
+
+/// Interrupt the profiled process group and wait for py-spy to write its
+/// output. Falls back to killing the whole group if it doesn't finish in
+/// time; the caller still kills and reaps `child` as usual.
+#[cfg(unix)]
+pub fn interrupt(child: &mut Child) {
+    let group = -(child.id() as libc::pid_t);
+    // SAFETY: signals a process group we created in `wrap_command`.
+    unsafe {
+        libc::kill(group, libc::SIGINT);
+    }
+    let deadline = Instant::now() + Duration::from_secs(PROFILE_FLUSH_SECS);
+    while Instant::now() < deadline {
+        if let Ok(Some(_)) = child.try_wait() {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(200));
+    }
+    eprintln!(
+        "[SOLAT] WARNING: Profiler did not exit within {}s, killing it",
+        PROFILE_FLUSH_SECS
+    );
+    // SAFETY: as above; py-spy hasn't been reaped, so the group id is still ours.
+    unsafe {
+        libc::kill(group, libc::SIGKILL);
+    }
+}
+
+#[cfg(windows)]
+pub fn interrupt(_child: &mut Child) {}
+
+/// Tell the frontend where the profile landed, if py-spy wrote one.
+pub fn announce(app: &AppHandle, output: &Path) {
+    if !output.exists() {
+        eprintln!(
+            "[SOLAT] WARNING: Profiled engine stopped but no profile was written to {}",
+            output.display()
+        );
+        return;
+    }
+    println!("[SOLAT] Profile written to {}", output.display());
+    let event = ProfileReadyEvent {
+        path: output.display().to_string(),
+    };
+    if let Err(e) = app.emit(PROFILE_READY_EVENT, &event) {
+        eprintln!("[SOLAT] WARNING: Failed to emit profile-ready: {}", e);
+    }
+}
@@ -0,0 +1,147 @@
+//! Scheduled engine restarts, for long-running installs where memory creeps.
+//!
+//! A timer thread checks `restart_schedule` every `TICK_SECS`. Once a restart
+//! is due it waits for the engine to be idle (no in-flight requests per
+//! `/admin/inflight`; an engine without that endpoint counts as idle) and
+//! then restarts it, announcing `engine://scheduled-restart` before and
+//! after. A restart that stays deferred keeps being retried until it runs.
+
+use std::time::{Duration, Instant};
+
+use chrono::{Local, NaiveDate};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::config::{self, RestartSchedule};
+use crate::sync::LockOrRecover;
+use crate::{EngineConfigState, EngineLogPath, EngineProcess};
+
+const SCHEDULED_RESTART_EVENT: &str = "engine://scheduled-restart";
+const TICK_SECS: u64 = 30;
+
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum RestartPhase {
+    Starting,
+    Finished,
+    Failed,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct ScheduledRestartEvent {
+    phase: RestartPhase,
+    message: String,
+}
+
+fn announce(app: &AppHandle, phase: RestartPhase, message: String) {
+    let event = ScheduledRestartEvent { phase, message };
+    if let Err(e) = app.emit(SCHEDULED_RESTART_EVENT, &event) {
+        eprintln!("[SOLAT] WARNING: Failed to emit scheduled-restart: {}", e);
+    }
+}
+
+/// When the last scheduled restart (or the scheduler's start) happened.
+struct LastRun {
+    at: Instant,
+    /// Local date of the last `daily_at` restart, or of today if the
+    /// scheduler started after today's slot: a launch at 10:00 shouldn't
+    /// count as having missed a 03:30 restart.
+    daily_on: NaiveDate,
+}
+
+fn is_due(schedule: &RestartSchedule, last: &LastRun) -> bool {
+    if let Some(hours) = schedule.every_hours {
+        return last.at.elapsed() >= Duration::from_secs(hours.saturating_mul(3600));
+    }
+    let Some(Ok(at)) = schedule.daily_at.as_deref().map(config::parse_time_of_day) else {
+        return false;
+    };
+    let now = Local::now();
+    now.date_naive() > last.daily_on && now.time() >= at
+}
+
+/// Whether the engine has no requests in flight.
+fn engine_is_idle(app: &AppHandle, config: &config::EngineConfig) -> Result<bool, String> {
+    let addr = crate::engine_addr(app, config)?;
+    let client = app.state::<crate::http::HttpClient>().0.clone();
+    let inflight = tauri::async_runtime::block_on(async {
+        crate::http::inflight_requests(&client, addr).await
+    })?;
+    Ok(inflight.is_none_or(|count| count == 0))
+}
+
+pub fn spawn_restart_scheduler(app: AppHandle) {
+    std::thread::spawn(move || {
+        let started_after_slot = app
+            .state::<EngineConfigState>()
+            .0
+            .lock_or_recover()
+            .restart_schedule
+            .as_ref()
+            .and_then(|schedule| schedule.daily_at.as_deref())
+            .and_then(|time| config::parse_time_of_day(time).ok())
+            .is_some_and(|at| Local::now().time() >= at);
+        let today = Local::now().date_naive();
+        let daily_on = if started_after_slot {
+            today
+        } else {
+            today.pred_opt().unwrap_or(today)
+        };
+        let mut last = LastRun {
+            at: Instant::now(),
+            daily_on,
+        };
+        let mut deferred = false;
+
+        loop {
+            std::thread::sleep(Duration::from_secs(TICK_SECS));
+
+            let config = app.state::<EngineConfigState>().current();
+            let Some(schedule) = &config.restart_schedule else {
+                continue;
+            };
+            if !is_due(schedule, &last) {
+                continue;
+            }
+
+            let proc_state = app.state::<EngineProcess>();
+            let running = crate::child_liveness(&mut proc_state.0.lock_or_recover()).0;
+            if !running {
+                // Nothing to refresh; the watchdog owns a crashed engine
+                continue;
+            }
+            match engine_is_idle(&app, &config) {
+                Ok(true) => {}
+                Ok(false) | Err(_) => {
+                    if !deferred {
+                        println!(
+                            "[SOLAT] Scheduled restart due, waiting for the engine to go idle"
+                        );
+                        deferred = true;
+                    }
+                    continue;
+                }
+            }
+
+            deferred = false;
+            last = LastRun {
+                at: Instant::now(),
+                daily_on: Local::now().date_naive(),
+            };
+            println!("[SOLAT] Scheduled restart: restarting idle engine");
+            announce(
+                &app,
+                RestartPhase::Starting,
+                "Restarting the engine on schedule".to_string(),
+            );
+            let log_state = app.state::<EngineLogPath>();
+            match crate::launch_engine(&app, &proc_state, &log_state, &config, "scheduled restart")
+            {
+                Ok(message) => announce(&app, RestartPhase::Finished, message),
+                Err(e) => {
+                    eprintln!("[SOLAT] WARNING: Scheduled restart failed: {}", e);
+                    announce(&app, RestartPhase::Failed, e.to_string());
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,408 @@
+//! Engine supervision: a heartbeat/watchdog that restarts an engine which
+//! exited on its own or went missing across sleep, plus the restart history
+//! it (and manual restarts) record, a budget stopping crash loops, boot
+//! durations for startup estimates, and a guard holding the boot log to its
+//! size cap.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::sync::LockOrRecover;
+use crate::{logs, EngineConfigState, EngineLogPath, EngineProcess, LifecycleLock};
+
+/// How many restarts `get_restart_history` can report.
+const RESTART_HISTORY_LEN: usize = 20;
+const WATCHDOG_INTERVAL_SECS: u64 = 5;
+/// A heartbeat this late means the machine was asleep (or the thread starved).
+const RESUME_GAP_SECS: u64 = 30;
+const RESUME_HEALTH_ATTEMPTS: u32 = 3;
+const RESUMED_EVENT: &str = "engine://resumed";
+pub const LOG_EXCERPT_LINES: usize = 20;
+/// Successful boots the startup estimate is the median of.
+const BOOT_HISTORY_LEN: usize = 10;
+/// Short, since a runaway engine can write a lot between checks.
+const LOG_GUARD_INTERVAL_SECS: u64 = 1;
+/// More watchdog restarts than this within `RESTART_WINDOW` is a crash loop.
+const RESTART_BUDGET: usize = 5;
+const RESTART_WINDOW: Duration = Duration::from_secs(60);
+/// An engine up this long proves the problem gone and refills the budget.
+const BUDGET_RESET_UPTIME: Duration = Duration::from_secs(120);
+const RESTART_EXHAUSTED_EVENT: &str = "engine://restart-exhausted";
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RestartRecord {
+    /// RFC 3339, UTC.
+    pub timestamp: String,
+    pub reason: String,
+    /// Exit status of the replaced engine, if it had already exited.
+    pub exit_status: Option<String>,
+    /// Tail of the replaced engine's log, captured before the new spawn.
+    pub log_excerpt: String,
+}
+
+/// Bounded ring buffer of the most recent restarts, oldest first.
+pub struct RestartHistory(Mutex<VecDeque<RestartRecord>>);
+
+impl RestartHistory {
+    pub fn new() -> Self {
+        Self(Mutex::new(VecDeque::with_capacity(RESTART_HISTORY_LEN)))
+    }
+
+    pub fn record(&self, record: RestartRecord) {
+        let mut history = self.0.lock_or_recover();
+        if history.len() == RESTART_HISTORY_LEN {
+            history.pop_front();
+        }
+        history.push_back(record);
+    }
+
+    pub fn snapshot(&self) -> Vec<RestartRecord> {
+        self.0.lock_or_recover().iter().cloned().collect()
+    }
+}
+
+#[derive(Default)]
+struct BudgetState {
+    /// Recent watchdog restarts and the crash behind each, oldest first.
+    recent: VecDeque<(Instant, String)>,
+    /// Set once the budget runs out; only a reset clears it.
+    exhausted: bool,
+}
+
+/// Caps watchdog restarts, so an engine that crashes on every boot (a bad
+/// config, say) isn't relaunched forever.
+pub struct RestartBudget(Mutex<BudgetState>);
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct RestartExhaustedEvent {
+    restarts: usize,
+    window_secs: u64,
+    /// Why each of those restarts was needed, oldest first.
+    errors: Vec<String>,
+}
+
+impl RestartBudget {
+    pub fn new() -> Self {
+        Self(Mutex::new(BudgetState::default()))
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.0.lock_or_recover().exhausted
+    }
+
+    /// Count a restart for `reason`. Once that is more than the budget
+    /// allows within the window, refuses and returns every reason in it.
+    fn spend(&self, reason: String) -> Result<(), Vec<String>> {
+        let mut state = self.0.lock_or_recover();
+        let now = Instant::now();
+        while state
+            .recent
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) > RESTART_WINDOW)
+        {
+            state.recent.pop_front();
+        }
+        state.recent.push_back((now, reason));
+        if state.recent.len() > RESTART_BUDGET {
+            state.exhausted = true;
+            return Err(state
+                .recent
+                .iter()
+                .map(|(_, reason)| reason.clone())
+                .collect());
+        }
+        Ok(())
+    }
+
+    pub fn reset(&self) {
+        *self.0.lock_or_recover() = BudgetState::default();
+    }
+}
+
+/// Heartbeat thread: relaunches an engine that exited without being asked
+/// to, and re-checks the engine after the machine wakes from sleep.
+///
+/// A relaunch that itself fails leaves no managed child, so a broken engine
+/// isn't restarted in a tight loop; one that boots and then keeps crashing
+/// is stopped by `RestartBudget`.
+pub fn spawn_watchdog(app: AppHandle) {
+    std::thread::spawn(move || {
+        // Wall clock rather than Instant: monotonic clocks stop while the
+        // machine is suspended, which would hide exactly the gap we look for.
+        let mut last_tick = SystemTime::now();
+        loop {
+            std::thread::sleep(Duration::from_secs(WATCHDOG_INTERVAL_SECS));
+
+            let now = SystemTime::now();
+            let gap = now.duration_since(last_tick).unwrap_or_default();
+            last_tick = now;
+
+            if gap >= Duration::from_secs(RESUME_GAP_SECS) {
+                handle_resume(&app, gap);
+            } else {
+                restart_if_crashed(&app);
+            }
+        }
+    });
+}
+
+/// Polls the active boot log and enforces `max_log_bytes` on it, so an
+/// engine logging in a tight loop can't fill the disk.
+pub fn spawn_log_guard(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut last_rotation: Option<Instant> = None;
+        loop {
+            std::thread::sleep(Duration::from_secs(LOG_GUARD_INTERVAL_SECS));
+
+            let max_bytes = app
+                .state::<EngineConfigState>()
+                .0
+                .lock_or_recover()
+                .max_log_bytes;
+            if max_bytes == 0 {
+                continue;
+            }
+            let log_path = app.state::<EngineLogPath>().0.lock_or_recover().clone();
+            logs::enforce_size_cap(&log_path, max_bytes, &mut last_rotation);
+        }
+    });
+}
+
+fn restart_if_crashed(app: &AppHandle) {
+    if !app.state::<EngineConfigState>().current().auto_restart {
+        return;
+    }
+    // A launch or stop in progress handles its own child's exit
+    if app.state::<LifecycleLock>().is_busy() {
+        return;
+    }
+
+    let proc_state = app.state::<EngineProcess>();
+    let (running, exit_status) = match proc_state
+        .0
+        .lock_or_recover()
+        .as_mut()
+        .map(|child| child.try_wait())
+    {
+        Some(Ok(None)) => (true, None),
+        Some(Ok(Some(status))) => (false, Some(status)),
+        _ => (false, None),
+    };
+    let budget = app.state::<RestartBudget>();
+    if running && crate::engine_uptime(app).is_some_and(|up| up >= BUDGET_RESET_UPTIME) {
+        budget.reset();
+    }
+    let Some(status) = exit_status else {
+        return;
+    };
+    if budget.is_exhausted() {
+        return;
+    }
+
+    let log_state = app.state::<EngineLogPath>();
+    let tail = logs::read_log_tail(&log_state.0.lock_or_recover(), LOG_EXCERPT_LINES);
+    let exit = crate::process::explain_exit(status, crate::launched_memory_limit(app), &tail);
+    if let Err(errors) = budget.spend(exit.clone()) {
+        eprintln!(
+            "[SOLAT] Watchdog: engine crashed {} times within {}s ({}), giving up on restarts",
+            errors.len(),
+            RESTART_WINDOW.as_secs(),
+            exit
+        );
+        let event = RestartExhaustedEvent {
+            restarts: errors.len(),
+            window_secs: RESTART_WINDOW.as_secs(),
+            errors,
+        };
+        if let Err(e) = app.emit(RESTART_EXHAUSTED_EVENT, &event) {
+            eprintln!("[SOLAT] WARNING: Failed to emit restart-exhausted: {}", e);
+        }
+        return;
+    }
+    eprintln!(
+        "[SOLAT] Watchdog: engine exited unexpectedly ({}), restarting...",
+        exit
+    );
+    // Relaunch as the crashed engine was, keeping safe mode or profiling
+    let config = crate::running_config(app);
+    match crate::launch_engine(
+        app,
+        &proc_state,
+        &log_state,
+        &config,
+        "watchdog auto-restart",
+    ) {
+        Ok(msg) => println!("[SOLAT] Watchdog: {}", msg),
+        Err(e) => eprintln!("[SOLAT] Watchdog: restart failed: {}", e),
+    }
+}
+
+#[derive(Default)]
+struct BootTimes {
+    /// Spawn-to-healthy durations of recent successful boots, oldest first.
+    recent: VecDeque<Duration>,
+    /// Start of the boot in progress, if any.
+    current: Option<Instant>,
+    /// Latest successful boot, kept past `recent` rolling it out.
+    last: Option<Duration>,
+    /// Health-confirmed boots since the launcher started.
+    boot_count: u32,
+}
+
+/// Startup-duration history, for predicting how long a boot will take.
+pub struct EngineStats(Mutex<BootTimes>);
+
+/// Boot-time signal for noticing when engine startup regresses.
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+pub struct EngineMetrics {
+    /// Spawn-to-healthy time of the latest successful boot.
+    pub last_boot_ms: Option<u64>,
+    pub boot_count: u32,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct StartupEta {
+    /// Time left in the boot in progress, or the whole expected boot when
+    /// none is running. Zero once a boot runs past the median.
+    pub remaining_ms: u64,
+    pub median_ms: u64,
+    pub samples: usize,
+}
+
+impl EngineStats {
+    pub fn new() -> Self {
+        Self(Mutex::new(BootTimes::default()))
+    }
+
+    pub fn boot_started(&self, at: Instant) {
+        self.0.lock_or_recover().current = Some(at);
+    }
+
+    /// End the boot in progress, recording its duration if it came up healthy.
+    pub fn boot_finished(&self, healthy: bool) {
+        let mut times = self.0.lock_or_recover();
+        let Some(started) = times.current.take() else {
+            return;
+        };
+        if healthy {
+            let elapsed = started.elapsed();
+            if times.recent.len() == BOOT_HISTORY_LEN {
+                times.recent.pop_front();
+            }
+            times.recent.push_back(elapsed);
+            times.last = Some(elapsed);
+            times.boot_count = times.boot_count.saturating_add(1);
+        }
+    }
+
+    pub fn metrics(&self) -> EngineMetrics {
+        let times = self.0.lock_or_recover();
+        EngineMetrics {
+            last_boot_ms: times.last.map(crate::telemetry::millis),
+            boot_count: times.boot_count,
+        }
+    }
+
+    fn median(times: &BootTimes) -> Option<Duration> {
+        let mut sorted: Vec<Duration> = times.recent.iter().copied().collect();
+        sorted.sort();
+        let mid = sorted.len() / 2;
+        match sorted.len() {
+            0 => None,
+            n if n % 2 == 0 => Some((sorted[mid - 1] + sorted[mid]) / 2),
+            _ => Some(sorted[mid]),
+        }
+    }
+
+    /// Predicted time left in the current boot (or a whole boot, if none is
+    /// running), from the median of recent successful boots. `None` until
+    /// there has been one.
+    pub fn estimate_startup_eta(&self) -> Option<StartupEta> {
+        let times = self.0.lock_or_recover();
+        let median = Self::median(&times)?;
+        let elapsed = times
+            .current
+            .map(|started| started.elapsed())
+            .unwrap_or_default();
+        Some(StartupEta {
+            remaining_ms: crate::telemetry::millis(median.saturating_sub(elapsed)),
+            median_ms: crate::telemetry::millis(median),
+            samples: times.recent.len(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct ResumedEvent {
+    gap_secs: u64,
+    restarted: bool,
+    error: Option<String>,
+}
+
+fn handle_resume(app: &AppHandle, gap: Duration) {
+    println!(
+        "[SOLAT] Heartbeat gap of {}s, assuming resume from sleep",
+        gap.as_secs()
+    );
+
+    // Only an engine that's meant to be running is brought back; a manual
+    // stop before sleep is respected.
+    let managed = app.state::<EngineProcess>().0.lock_or_recover().is_some();
+
+    let (restarted, error) = if managed {
+        match ensure_engine_running(app) {
+            Ok(restarted) => (restarted, None),
+            Err(e) => {
+                eprintln!("[SOLAT] Failed to recover engine after resume: {}", e);
+                (false, Some(e))
+            }
+        }
+    } else {
+        (false, None)
+    };
+
+    let event = ResumedEvent {
+        gap_secs: gap.as_secs(),
+        restarted,
+        error,
+    };
+    if let Err(e) = app.emit(RESUMED_EVENT, &event) {
+        eprintln!("[SOLAT] WARNING: Failed to emit resume event: {}", e);
+    }
+}
+
+/// Relaunch the engine unless the managed child is alive and answering
+/// `/health`. Returns whether a relaunch happened.
+pub fn ensure_engine_running(app: &AppHandle) -> Result<bool, String> {
+    let config = crate::running_config(app);
+    let proc_state = app.state::<EngineProcess>();
+    let (running, _pid) = crate::child_liveness(&mut proc_state.0.lock_or_recover());
+
+    if running {
+        // The network stack can take a moment after wake; retry before
+        // concluding the engine is wedged.
+        for attempt in 0..RESUME_HEALTH_ATTEMPTS {
+            if crate::probe_health(app, &config).0 {
+                return Ok(false);
+            }
+            if attempt + 1 < RESUME_HEALTH_ATTEMPTS {
+                std::thread::sleep(Duration::from_secs(1));
+            }
+        }
+        eprintln!("[SOLAT] Engine process alive but not answering health, restarting");
+    }
+
+    let log_state = app.state::<EngineLogPath>();
+    crate::launch_engine(
+        app,
+        &proc_state,
+        &log_state,
+        &config,
+        "recovery after resume",
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(true)
+}
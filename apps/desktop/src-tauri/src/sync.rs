@@ -0,0 +1,28 @@
+//! Locking that survives a panic elsewhere.
+//!
+//! A thread that panics while holding a state mutex poisons it, and every
+//! later `lock()` fails: one crashed background thread would leave the
+//! lifecycle commands erroring for the rest of the session. The state behind
+//! these locks is always left consistent between statements, so it's safe to
+//! carry on with it.
+
+use std::sync::{Mutex, MutexGuard};
+
+pub trait LockOrRecover<T> {
+    /// Lock, taking over a poisoned lock (with a warning) instead of failing.
+    fn lock_or_recover(&self) -> MutexGuard<'_, T>;
+}
+
+impl<T> LockOrRecover<T> for Mutex<T> {
+    fn lock_or_recover(&self) -> MutexGuard<'_, T> {
+        self.lock().unwrap_or_else(|poisoned| {
+            eprintln!(
+                "[SOLAT] WARNING: Recovering {} from a thread that panicked holding it",
+                std::any::type_name::<T>()
+            );
+            // Warn once, not on every later lock
+            self.clear_poison();
+            poisoned.into_inner()
+        })
+    }
+}
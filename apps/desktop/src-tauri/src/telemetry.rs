@@ -0,0 +1,54 @@
+//! Opt-in engine lifecycle telemetry.
+//!
+//! Events are only ever emitted to the frontend as Tauri events (nothing
+//! leaves the machine); the frontend decides whether to aggregate them.
+//! Nothing is emitted unless `telemetry_enabled` is set in the config.
+
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter};
+
+use crate::config::EngineConfig;
+
+pub const TELEMETRY_EVENT: &str = "engine://telemetry";
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TelemetryEvent {
+    SpawnAttempt {
+        safe_mode: bool,
+    },
+    /// `health_confirmed` is false for the non-blocking startup spawn, where
+    /// health is left to the splash screen.
+    SpawnSuccess {
+        pid: u32,
+        duration_ms: u64,
+        health_confirmed: bool,
+    },
+    SpawnFailure {
+        reason: String,
+        duration_ms: u64,
+    },
+    HealthTimeout {
+        pid: u32,
+        duration_ms: u64,
+    },
+    /// A start replaced a managed engine that had already exited on its own.
+    CrashRestart {
+        exit_status: String,
+        duration_ms: u64,
+    },
+}
+
+pub fn emit(app: &AppHandle, config: &EngineConfig, event: TelemetryEvent) {
+    if !config.telemetry_enabled {
+        return;
+    }
+    if let Err(e) = app.emit(TELEMETRY_EVENT, &event) {
+        eprintln!("[SOLAT] WARNING: Failed to emit telemetry event: {}", e);
+    }
+}
+
+pub fn millis(duration: Duration) -> u64 {
+    duration.as_millis() as u64
+}
@@ -0,0 +1,314 @@
+//! Diagnosis and repair of the engine's `.venv`.
+//!
+//! Repair is a fixed ladder, each step tried at most once: `uv sync
+//! --reinstall`, then deleting `.venv` and syncing from scratch. Each step is
+//! followed by a fresh diagnosis, and the ladder stops as soon as the venv
+//! checks out. Progress (and uv's own output) goes to the boot log so the
+//! log view shows it live.
+//!
+//! Without uv, `SOLAT_AUTO_SETUP=1` lets a launch bootstrap a missing venv
+//! with the stdlib `venv` module and pip instead.
+
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+
+use crate::process::wait_bounded;
+
+/// A sync downloads packages, so allow it a while, but not forever.
+const STEP_TIMEOUT_SECS: u64 = 600;
+/// Quick import / version probes.
+const PROBE_TIMEOUT_SECS: u64 = 30;
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RepairStep {
+    /// Command line that was run.
+    pub action: String,
+    pub success: bool,
+    /// Exit status, or why the step couldn't run.
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RepairReport {
+    /// Problems found before any repair was attempted.
+    pub problems: Vec<String>,
+    pub steps: Vec<RepairStep>,
+    /// Problems left after the last step; empty means the venv is usable.
+    pub remaining_problems: Vec<String>,
+}
+
+/// The interpreter inside the engine's `.venv`, where `bootstrap` and uv
+/// put it on this platform.
+pub fn venv_python(engine_dir: &Path) -> PathBuf {
+    if cfg!(windows) {
+        engine_dir.join(".venv/Scripts/python.exe")
+    } else {
+        engine_dir.join(".venv/bin/python3")
+    }
+}
+
+/// Lower bound of `requires-python = ">=X.Y"` in the engine's pyproject.
+fn required_python(engine_dir: &Path) -> Option<(u32, u32)> {
+    let pyproject = fs::read_to_string(engine_dir.join("pyproject.toml")).ok()?;
+    let spec = pyproject
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("requires-python"))?;
+    let version = spec.split(">=").nth(1)?;
+    let version: String = version
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    let mut parts = version.split('.').map(|p| p.parse::<u32>().ok());
+    Some((parts.next()??, parts.next().flatten().unwrap_or(0)))
+}
+
+/// Run a short probe to completion, returning its status and combined
+/// output. Probe output is small, so it's read only once the probe exits.
+pub fn probe(command: &mut Command, timeout: u64) -> Result<(ExitStatus, String), String> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    let status = wait_bounded(&mut child, timeout)?;
+    let mut text = String::new();
+    if let Some(mut stdout) = child.stdout.take() {
+        let _ = stdout.read_to_string(&mut text);
+    }
+    if let Some(mut stderr) = child.stderr.take() {
+        let _ = stderr.read_to_string(&mut text);
+    }
+    Ok((status, text.trim().to_string()))
+}
+
+/// Everything wrong with the venv, or an empty list if it looks usable.
+pub fn diagnose(engine_dir: &Path, uv: Option<&Path>) -> Vec<String> {
+    let python = venv_python(engine_dir);
+    if !engine_dir.join(".venv").is_dir() {
+        return vec!["No .venv in the engine directory".to_string()];
+    }
+    if !python.exists() {
+        return vec![format!("Venv has no interpreter at {}", python.display())];
+    }
+
+    let mut problems = Vec::new();
+    match probe(
+        Command::new(&python).args(["-c", "import sys; print('%d.%d' % sys.version_info[:2])"]),
+        PROBE_TIMEOUT_SECS,
+    ) {
+        Ok((status, out)) if status.success() => {
+            let found = out.split_once('.').and_then(|(major, minor)| {
+                Some((major.parse::<u32>().ok()?, minor.parse::<u32>().ok()?))
+            });
+            if let (Some(found), Some(required)) = (found, required_python(engine_dir)) {
+                if found < required {
+                    problems.push(format!(
+                        "Venv Python is {}.{}, engine requires >={}.{}",
+                        found.0, found.1, required.0, required.1
+                    ));
+                }
+            }
+        }
+        Ok((_, out)) | Err(out) => {
+            // An interpreter that can't even report its version is broken
+            // beyond any package-level check.
+            return vec![format!("Venv interpreter does not run: {}", out)];
+        }
+    }
+
+    match probe(
+        Command::new(&python).args(["-c", "import uvicorn"]),
+        PROBE_TIMEOUT_SECS,
+    ) {
+        Ok((status, _)) if status.success() => {}
+        Ok((_, out)) | Err(out) => problems.push(format!("uvicorn is not importable: {}", out)),
+    }
+
+    if let Some(uv) = uv {
+        match probe(
+            Command::new(uv)
+                .args(["pip", "check", "--python"])
+                .arg(&python)
+                .current_dir(engine_dir),
+            PROBE_TIMEOUT_SECS,
+        ) {
+            Ok((status, _)) if status.success() => {}
+            Ok((_, out)) => problems.push(format!("site-packages is inconsistent: {}", out)),
+            Err(e) => problems.push(format!("Could not check site-packages: {}", e)),
+        }
+    }
+
+    problems
+}
+
+fn log_line(log: &mut File, message: &str) {
+    println!("[SOLAT] {}", message);
+    let _ = writeln!(log, "[SOLAT] {}", message);
+}
+
+/// Run one step with its output appended to `log`, logged under `label`.
+fn run_step(log: &mut File, label: &str, command: &mut Command) -> RepairStep {
+    let action = crate::process::describe_command(command);
+    log_line(log, &format!("{}: running {}", label, action));
+
+    let result = (|| {
+        let stdout = log.try_clone().map_err(|e| e.to_string())?;
+        let stderr = log.try_clone().map_err(|e| e.to_string())?;
+        let mut child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::from(stdout))
+            .stderr(Stdio::from(stderr))
+            .spawn()
+            .map_err(|e| format!("failed to start: {}", e))?;
+        wait_bounded(&mut child, STEP_TIMEOUT_SECS)
+    })();
+
+    let step = match result {
+        Ok(status) => RepairStep {
+            action,
+            success: status.success(),
+            detail: status.to_string(),
+        },
+        Err(detail) => RepairStep {
+            action,
+            success: false,
+            detail,
+        },
+    };
+    log_line(
+        log,
+        &format!(
+            "{}: {} ({})",
+            label,
+            if step.success {
+                "step succeeded"
+            } else {
+                "step failed"
+            },
+            step.detail
+        ),
+    );
+    step
+}
+
+/// Whether launches may create a missing venv themselves (`SOLAT_AUTO_SETUP=1`).
+pub fn auto_setup_enabled() -> bool {
+    std::env::var("SOLAT_AUTO_SETUP").is_ok_and(|v| v.trim() == "1")
+}
+
+/// Create `.venv` with `python` and install the engine into it in editable
+/// mode, with progress and pip's output appended to `log`.
+pub fn bootstrap(engine_dir: &Path, python: &Path, log: &mut File) -> Result<(), String> {
+    log_line(
+        log,
+        &format!("Auto setup: creating a venv in {}", engine_dir.display()),
+    );
+    let mut create = Command::new(python);
+    create.args(["-m", "venv", ".venv"]).current_dir(engine_dir);
+    let mut install = Command::new(venv_python(engine_dir));
+    install
+        .args(["-m", "pip", "install", "-e", "."])
+        .current_dir(engine_dir);
+
+    for command in [&mut create, &mut install] {
+        let step = run_step(log, "Auto setup", command);
+        if !step.success {
+            return Err(format!("{} failed ({})", step.action, step.detail));
+        }
+    }
+    log_line(log, "Auto setup: engine installed");
+    Ok(())
+}
+
+/// Diagnose the venv and, if needed, walk the repair ladder. The engine must
+/// not be running: it holds files in the venv open.
+pub fn repair(
+    engine_dir: &Path,
+    uv: Option<&Path>,
+    log_path: &Path,
+) -> Result<RepairReport, String> {
+    let mut log = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path)
+        .map_err(|e| format!("Failed to open log file: {}", e))?;
+
+    log_line(
+        &mut log,
+        &format!("Venv repair: checking {}", engine_dir.display()),
+    );
+    let problems = diagnose(engine_dir, uv);
+    let mut report = RepairReport {
+        problems: problems.clone(),
+        steps: Vec::new(),
+        remaining_problems: problems,
+    };
+    if report.problems.is_empty() {
+        log_line(&mut log, "Venv repair: no problems found");
+        return Ok(report);
+    }
+    for problem in &report.problems {
+        log_line(
+            &mut log,
+            &format!("Venv repair: found problem: {}", problem),
+        );
+    }
+
+    let Some(uv) = uv else {
+        report.steps.push(RepairStep {
+            action: "uv sync".to_string(),
+            success: false,
+            detail: "uv not found; install uv to repair the venv".to_string(),
+        });
+        log_line(&mut log, "Venv repair: uv not found, nothing attempted");
+        return Ok(report);
+    };
+
+    let mut reinstall = Command::new(uv);
+    reinstall
+        .args(["sync", "--reinstall"])
+        .current_dir(engine_dir);
+    report
+        .steps
+        .push(run_step(&mut log, "Venv repair", &mut reinstall));
+    report.remaining_problems = diagnose(engine_dir, Some(uv));
+
+    if !report.remaining_problems.is_empty() {
+        let venv = engine_dir.join(".venv");
+        log_line(
+            &mut log,
+            &format!("Venv repair: recreating {}", venv.display()),
+        );
+        match fs::remove_dir_all(&venv) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => report.steps.push(RepairStep {
+                action: format!("remove {}", venv.display()),
+                success: false,
+                detail: e.to_string(),
+            }),
+            _ => {
+                let mut sync = Command::new(uv);
+                sync.arg("sync").current_dir(engine_dir);
+                report
+                    .steps
+                    .push(run_step(&mut log, "Venv repair", &mut sync));
+            }
+        }
+        report.remaining_problems = diagnose(engine_dir, Some(uv));
+    }
+
+    log_line(
+        &mut log,
+        &if report.remaining_problems.is_empty() {
+            "Venv repair: venv is healthy".to_string()
+        } else {
+            format!(
+                "Venv repair: {} problem(s) remain",
+                report.remaining_problems.len()
+            )
+        },
+    );
+    Ok(report)
+}
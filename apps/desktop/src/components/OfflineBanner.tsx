@@ -3,6 +3,7 @@
  */
 
 import type { ConnectionState } from "../hooks/useEngineHealth";
+import { useEnginePort } from "../hooks/useEnginePort";
 
 interface OfflineBannerProps {
   connectionState: ConnectionState;
@@ -23,6 +24,8 @@ export function OfflineBanner({
   onStartEngine,
   isStartingEngine = false,
 }: OfflineBannerProps) {
+  const port = useEnginePort();
+
   if (connectionState === "connected") {
     return null;
   }
@@ -52,7 +55,7 @@ export function OfflineBanner({
             <span className="offline-hint">
               {error
                 ? error
-                : "Check that the SOLAT engine is running on port 8765"}
+                : `Check that the SOLAT engine is running on port ${port}`}
             </span>
           )}
 
@@ -15,10 +15,11 @@ import { useCallback, useEffect, useRef, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { open } from "@tauri-apps/plugin-shell";
 import { engineClient } from "../lib/engineClient";
+import type { EngineLog } from "../hooks/useEngineLauncher";
+import { engineHttpUrl } from "../hooks/useEnginePort";
 
 const BOOT_TIMEOUT_MS = 15_000;
 const POLL_MS = 1_000;
-const ENGINE_URL = "http://127.0.0.1:8765";
 const MIN_SPLASH_MS = 8_000; // Match video duration — always play full animation
 
 type BootStage = "shell" | "engine" | "catalogue" | "ready" | "failed";
@@ -76,7 +77,7 @@ export function SplashScreen({ onReady, onStartEngine }: SplashScreenProps) {
 
     while (Date.now() < deadline && !isStale()) {
       try {
-        const res = await fetch(`${ENGINE_URL}/health`, {
+        const res = await fetch(`${engineHttpUrl()}/health`, {
           signal: AbortSignal.timeout(2000),
         });
         if (res.ok) {
@@ -199,8 +200,10 @@ export function SplashScreen({ onReady, onStartEngine }: SplashScreenProps) {
       await open(logPath);
     } catch {
       try {
-        const log = await invoke<string>("get_engine_log");
-        setLogTail(log);
+        const log = await invoke<EngineLog>("get_engine_log");
+        setLogTail(
+          log.log_exists ? log.content : "(the engine has not written a log yet)",
+        );
       } catch {
         // ignore
       }
@@ -4,6 +4,7 @@ import { DiagnosticsPanel } from "./DiagnosticsPanel";
 import { ExecutionPanel } from "./ExecutionPanel";
 import { InfoTip } from "./InfoTip";
 import { useAutopilot } from "../hooks/useAutopilot";
+import { useEnginePort } from "../hooks/useEnginePort";
 import { useFlashOnChange } from "../hooks/useFlashOnChange";
 import { useTerminalSignals } from "../hooks/useTerminalSignals";
 
@@ -274,6 +275,8 @@ export function StatusScreen({
   onStartEngine,
   isStartingEngine = false,
 }: StatusScreenProps) {
+  const port = useEnginePort();
+
   if (isLoading) {
     return (
       <div className="status-screen">
@@ -293,7 +296,7 @@ export function StatusScreen({
           <h2 className="error-title">Engine Connection Failed</h2>
           <p className="error-message">
             Unable to connect to the SOLAT engine. Make sure the Python sidecar is running
-            on port 8765.
+            on port {port}.
           </p>
           <p className="error-message" style={{ marginTop: 8, fontSize: 12, opacity: 0.7 }}>
             {error}
@@ -8,8 +8,8 @@
  */
 
 import { useCallback, useEffect, useRef, useState } from "react";
+import { engineHttpUrl } from "./useEnginePort";
 
-const ENGINE_URL = "http://127.0.0.1:8765";
 
 // Retry configuration
 const INITIAL_DELAY_MS = 3000; // Wait for engine to start before first check
@@ -131,8 +131,8 @@ export function useEngineHealth(): UseEngineHealthResult {
       const timeoutId = setTimeout(() => controller.abort(), 5000);
 
       const [healthRes, configRes] = await Promise.all([
-        fetch(`${ENGINE_URL}/health`, { signal: controller.signal }),
-        fetch(`${ENGINE_URL}/config`, { signal: controller.signal }),
+        fetch(`${engineHttpUrl()}/health`, { signal: controller.signal }),
+        fetch(`${engineHttpUrl()}/config`, { signal: controller.signal }),
       ]);
 
       clearTimeout(timeoutId);
@@ -16,11 +16,31 @@ export interface EngineStatus {
   log_path: string;
 }
 
+/** `get_engine_log` result; `log_exists` is false until the engine first writes a log. */
+export interface EngineLog {
+  content: string;
+  log_exists: boolean;
+}
+
+/** Engine commands reject with `{ code, message, ... }`. */
+export interface EngineCommandError {
+  code: string;
+  message: string;
+}
+
+function errorMessage(err: unknown): string {
+  if (err instanceof Error) return err.message;
+  if (typeof err === "object" && err !== null && "message" in err) {
+    return String((err as EngineCommandError).message);
+  }
+  return String(err);
+}
+
 interface UseEngineLauncherResult {
   startEngine: () => Promise<void>;
   stopEngine: () => Promise<void>;
   getEngineStatus: () => Promise<EngineStatus>;
-  getEngineLog: () => Promise<string>;
+  getEngineLog: () => Promise<EngineLog>;
   isStarting: boolean;
   lastMessage: string | null;
   lastError: string | null;
@@ -35,10 +55,10 @@ export function useEngineLauncher(): UseEngineLauncherResult {
     setIsStarting(true);
     setLastError(null);
     try {
-      const msg = await invoke<string>("start_engine");
-      setLastMessage(msg);
+      const started = await invoke<{ message: string }>("start_engine");
+      setLastMessage(started.message);
     } catch (err) {
-      const message = err instanceof Error ? err.message : String(err);
+      const message = errorMessage(err);
       setLastError(message);
     } finally {
       setIsStarting(false);
@@ -50,7 +70,7 @@ export function useEngineLauncher(): UseEngineLauncherResult {
       const msg = await invoke<string>("stop_engine");
       setLastMessage(msg);
     } catch (err) {
-      const message = err instanceof Error ? err.message : String(err);
+      const message = errorMessage(err);
       setLastError(message);
     }
   }, []);
@@ -59,8 +79,8 @@ export function useEngineLauncher(): UseEngineLauncherResult {
     return invoke<EngineStatus>("get_engine_status");
   }, []);
 
-  const getEngineLog = useCallback(async (): Promise<string> => {
-    return invoke<string>("get_engine_log");
+  const getEngineLog = useCallback(async (): Promise<EngineLog> => {
+    return invoke<EngineLog>("get_engine_log");
   }, []);
 
   return {
@@ -0,0 +1,70 @@
+/**
+ * The engine's current port, for building its HTTP and WebSocket URLs.
+ *
+ * The shell picks the port per launch (8765 unless that is taken, or any free
+ * port with `port: "auto"`), so nothing here may assume 8765. The port is read
+ * once via `get_engine_port` and then follows `engine://port`, which the shell
+ * emits on every launch, restarts on a new port included.
+ */
+
+import { useSyncExternalStore } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+/** Used until the shell answers, and in a plain browser where it never does. */
+const DEFAULT_ENGINE_PORT = 8765;
+
+let enginePort = DEFAULT_ENGINE_PORT;
+let tracking = false;
+const subscribers = new Set<() => void>();
+
+function setEnginePort(port: number) {
+  if (port === enginePort) return;
+  enginePort = port;
+  subscribers.forEach((notify) => notify());
+}
+
+function ensureTracking() {
+  if (tracking) return;
+  tracking = true;
+
+  // An event that lands first is newer than the answer to this query.
+  let announced = false;
+  listen<number>("engine://port", (event) => {
+    announced = true;
+    setEnginePort(event.payload);
+  }).catch(() => {});
+  invoke<number>("get_engine_port")
+    .then((port) => {
+      if (!announced) setEnginePort(port);
+    })
+    .catch(() => {});
+}
+
+function subscribe(notify: () => void) {
+  ensureTracking();
+  subscribers.add(notify);
+  return () => {
+    subscribers.delete(notify);
+  };
+}
+
+function getEnginePort() {
+  ensureTracking();
+  return enginePort;
+}
+
+/** `http://127.0.0.1:<port>`; read it per request rather than caching it. */
+export function engineHttpUrl(port = getEnginePort()): string {
+  return `http://127.0.0.1:${port}`;
+}
+
+/** `ws://127.0.0.1:<port>/ws` for the engine's event stream. */
+export function engineWsUrl(port = getEnginePort()): string {
+  return `ws://127.0.0.1:${port}/ws`;
+}
+
+/** The engine port, re-rendering the caller when a launch moves it. */
+export function useEnginePort(): number {
+  return useSyncExternalStore(subscribe, getEnginePort);
+}
@@ -1,6 +1,6 @@
 import { useCallback, useEffect, useState } from "react";
+import { engineHttpUrl } from "./useEnginePort";
 
-const ENGINE_URL = "http://127.0.0.1:8765";
 
 export interface ExecutionStatus {
   mode: string;
@@ -35,7 +35,7 @@ export function useExecutionStatus(): UseExecutionStatusResult {
 
   const fetchStatus = useCallback(async () => {
     try {
-      const res = await fetch(`${ENGINE_URL}/execution/status`);
+      const res = await fetch(`${engineHttpUrl()}/execution/status`);
       if (!res.ok) {
         throw new Error("Failed to fetch execution status");
       }
@@ -59,7 +59,7 @@ export function useExecutionStatus(): UseExecutionStatusResult {
 
   const connect = useCallback(async () => {
     try {
-      const res = await fetch(`${ENGINE_URL}/execution/connect`, {
+      const res = await fetch(`${engineHttpUrl()}/execution/connect`, {
         method: "POST",
       });
       const data = await res.json();
@@ -76,7 +76,7 @@ export function useExecutionStatus(): UseExecutionStatusResult {
 
   const disconnect = useCallback(async () => {
     try {
-      const res = await fetch(`${ENGINE_URL}/execution/disconnect`, {
+      const res = await fetch(`${engineHttpUrl()}/execution/disconnect`, {
         method: "POST",
       });
       if (res.ok) {
@@ -94,7 +94,7 @@ export function useExecutionStatus(): UseExecutionStatusResult {
   const arm = useCallback(
     async (confirm: boolean) => {
       try {
-        const res = await fetch(`${ENGINE_URL}/execution/arm`, {
+        const res = await fetch(`${engineHttpUrl()}/execution/arm`, {
           method: "POST",
           headers: { "Content-Type": "application/json" },
           body: JSON.stringify({ confirm }),
@@ -112,7 +112,7 @@ export function useExecutionStatus(): UseExecutionStatusResult {
 
   const disarm = useCallback(async () => {
     try {
-      const res = await fetch(`${ENGINE_URL}/execution/disarm`, {
+      const res = await fetch(`${engineHttpUrl()}/execution/disarm`, {
         method: "POST",
       });
       const data = await res.json();
@@ -126,7 +126,7 @@ export function useExecutionStatus(): UseExecutionStatusResult {
   const activateKillSwitch = useCallback(
     async (reason: string) => {
       try {
-        const res = await fetch(`${ENGINE_URL}/execution/kill-switch/activate`, {
+        const res = await fetch(`${engineHttpUrl()}/execution/kill-switch/activate`, {
           method: "POST",
           headers: { "Content-Type": "application/json" },
           body: JSON.stringify({ reason }),
@@ -143,7 +143,7 @@ export function useExecutionStatus(): UseExecutionStatusResult {
 
   const resetKillSwitch = useCallback(async () => {
     try {
-      const res = await fetch(`${ENGINE_URL}/execution/kill-switch/reset`, {
+      const res = await fetch(`${engineHttpUrl()}/execution/kill-switch/reset`, {
         method: "POST",
       });
       const data = await res.json();
@@ -1,6 +1,6 @@
 import { useCallback, useEffect, useRef, useState } from "react";
+import { engineWsUrl, useEnginePort } from "./useEnginePort";
 
-const WS_URL = "ws://127.0.0.1:8765/ws";
 const INITIAL_DELAY_MS = 3000; // Wait for engine to start before first attempt
 const MIN_RECONNECT_DELAY = 1000;
 const MAX_RECONNECT_DELAY = 15000;
@@ -35,7 +35,7 @@ export function useWebSocket(): UseWebSocketResult {
     }
 
     try {
-      const ws = new WebSocket(WS_URL);
+      const ws = new WebSocket(engineWsUrl());
 
       ws.onopen = () => {
         if (!mountedRef.current) return;
@@ -124,5 +124,20 @@ export function useWebSocket(): UseWebSocketResult {
     // eslint-disable-next-line react-hooks/exhaustive-deps
   }, []);
 
+  // A launch on another port leaves the old socket talking to nothing, so
+  // reconnect straight away instead of waiting out the backoff.
+  const port = useEnginePort();
+  const socketPortRef = useRef(port);
+  useEffect(() => {
+    if (socketPortRef.current === port) return;
+    socketPortRef.current = port;
+    if (reconnectTimeoutRef.current) {
+      clearTimeout(reconnectTimeoutRef.current);
+    }
+    reconnectAttemptsRef.current = 0;
+    connect();
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [port]);
+
   return { heartbeatCount, isConnected, connectionStatus, lastMessage };
 }
@@ -4,6 +4,7 @@
 
 import { useEffect, useRef } from "react";
 import { Bar } from "../lib/engineClient";
+import { engineWsUrl, useEnginePort } from "./useEnginePort";
 
 const DEBUG_INGEST_URL = "http://127.0.0.1:7245/ingest/b34e6a51-242b-4280-9e50-b775760b6116";
 
@@ -54,6 +55,7 @@ export type WsEvent =
   | HeartbeatEvent;
 
 interface UseWsEventsOptions {
+  /** Defaults to the engine's stream on its current port. */
   wsUrl?: string;
   onQuote?: (event: QuoteUpdateEvent) => void;
   onBar?: (event: BarUpdateEvent) => void;
@@ -65,7 +67,7 @@ interface UseWsEventsOptions {
 }
 
 export function useWsEvents({
-  wsUrl = "ws://127.0.0.1:8765/ws",
+  wsUrl: wsUrlOverride,
   onQuote,
   onBar,
   onMarketStatus,
@@ -74,6 +76,8 @@ export function useWsEvents({
   onConnect,
   onDisconnect,
 }: UseWsEventsOptions) {
+  const port = useEnginePort();
+  const wsUrl = wsUrlOverride ?? engineWsUrl(port);
   const wsRef = useRef<WebSocket | null>(null);
   const reconnectTimeoutRef = useRef<ReturnType<typeof setTimeout> | null>(null);
   const handlersRef = useRef({
@@ -16,6 +16,7 @@ import { save } from "@tauri-apps/plugin-dialog";
 import { writeTextFile } from "@tauri-apps/plugin-fs";
 import { open } from "@tauri-apps/plugin-shell";
 import { engineClient, DataSummaryResponse, DataSyncResponse } from "../lib/engineClient";
+import { useEnginePort } from "../hooks/useEnginePort";
 import { useLiveGates } from "../hooks/useLiveGates";
 import { InfoTip } from "../components/InfoTip";
 
@@ -37,6 +38,7 @@ export function SettingsScreen() {
   const [exportMessage, setExportMessage] = useState<string | null>(null);
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
+  const enginePort = useEnginePort();
 
   // Data sync state
   const [syncing, setSyncing] = useState(false);
@@ -231,7 +233,7 @@ export function SettingsScreen() {
       <section className="settings-section">
         <h3 className="section-title">
           Engine
-          <InfoTip text="Core engine configuration. The engine runs as a Python FastAPI sidecar on localhost:8765. Mode controls whether trades are paper (DEMO) or real (LIVE)." />
+          <InfoTip text="Core engine configuration. The engine runs as a Python FastAPI sidecar on localhost (port 8765 unless another app holds it). Mode controls whether trades are paper (DEMO) or real (LIVE)." />
         </h3>
         <div className="settings-grid">
           <div className="setting-row">
@@ -246,7 +248,7 @@ export function SettingsScreen() {
           </div>
           <div className="setting-row">
             <span className="setting-label">Port</span>
-            <span className="setting-value">8765</span>
+            <span className="setting-value">{enginePort}</span>
           </div>
           <div className="setting-row">
             <span className="setting-label">Data Directory</span>